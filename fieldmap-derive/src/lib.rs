@@ -2,18 +2,19 @@
 
 extern crate proc_macro;
 
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
+use proc_macro2::{Delimiter, Literal, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use rename::RenameRule;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
-use structmeta::StructMeta;
-use syn::punctuated::Punctuated;
+use structmeta::{Flag, NameArgs, StructMeta};
 use syn::spanned::Spanned;
-use syn::token::*;
 use syn::*;
-use utils::into_macro_output;
+use utils::{error_with_note, into_macro_output};
 
 #[macro_use]
 mod utils;
+mod rename;
 
 #[proc_macro_derive(Fields, attributes(fields))]
 pub fn derive_field_map(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -22,56 +23,177 @@ pub fn derive_field_map(input: proc_macro::TokenStream) -> proc_macro::TokenStre
     )))
 }
 fn derive_field_map_core(input: DeriveInput) -> Result<TokenStream> {
+    let args = ContainerArgs::from_attrs(&input.attrs)?;
+    if args.item_ids.is_empty() {
+        bail!(
+            input.span(),
+            "`#[fields(item = \"{{TraitName}}\"]` required."
+        );
+    }
     let mut ts = TokenStream::new();
-    if let Data::Struct(s) = &input.data {
-        if let Some(item_id) = get_item_trait(&input.attrs)? {
-            match &s.fields {
-                Fields::Named(fields) => {
-                    impl_field_map(&input, &item_id, &fields.named, &mut ts);
-                }
-                Fields::Unnamed(fields) => {
-                    impl_field_map(&input, &item_id, &fields.unnamed, &mut ts);
-                }
-                Fields::Unit => {
-                    impl_field_map(&input, &item_id, &Punctuated::new(), &mut ts);
-                }
+    match &input.data {
+        Data::Struct(s) => {
+            impl_field_map_struct(&input, &args, &s.fields, &mut ts)?;
+            if let Some((partial, _)) = &args.partial {
+                impl_partial(&input, &args, partial, &s.fields, &mut ts)?;
             }
-            Ok(ts)
-        } else {
-            bail!(
-                input.span(),
-                "`#[fields(item = \"{{TraitName}}\"]` required."
-            );
         }
-    } else {
-        bail!(input.span(), "`#[derive(Fields)]` supports only struct.");
+        Data::Enum(e) => {
+            if let Some((_, span)) = &args.partial {
+                bail!(*span, "`partial` can be used only with struct.");
+            }
+            impl_field_map_enum(&input, &args, e, &mut ts)?
+        }
+        Data::Union(_) => bail!(
+            input.span(),
+            "`#[derive(Fields)]` supports only struct and enum."
+        ),
     }
+    Ok(ts)
 }
 
 #[proc_macro_derive(Field)]
 pub fn derive_field(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+    into_macro_output(derive_field_core(parse_macro_input!(input as DeriveInput)))
+}
+fn derive_field_core(input: DeriveInput) -> Result<TokenStream> {
     let mut ts = TokenStream::new();
-    if let Data::Struct(s) = &input.data {
-        match &s.fields {
-            Fields::Named(fields) => {
-                impl_field_all(&input, &fields.named, &mut ts);
-            }
-            Fields::Unnamed(fields) => {
-                impl_field_all(&input, &fields.unnamed, &mut ts);
-            }
-            Fields::Unit => {}
+    match &input.data {
+        Data::Struct(s) => impl_field_all(&input, &s.fields, &mut ts)?,
+        Data::Enum(e) => impl_try_field_all(&input, e, &mut ts)?,
+        Data::Union(_) => bail!(
+            input.span(),
+            "`#[derive(Field)]` supports only struct and enum."
+        ),
+    }
+    Ok(ts)
+}
+
+fn impl_field_all(input: &DeriveInput, fields: &Fields, ts: &mut TokenStream) -> Result<()> {
+    let params = type_params(&input.generics);
+    for (idx, field) in fields.iter().enumerate() {
+        for other in fields.iter().take(idx) {
+            check_conflict(other, field, &params)?;
+        }
+    }
+
+    let tags_id = tags_ident(input);
+    let vis = &input.vis;
+    let self_id = &input.ident;
+    let mut tags = Vec::new();
+    for (idx, field) in fields.iter().enumerate() {
+        let tag = tag_ident(idx, field);
+        let doc = format!(
+            "Tag of field `{}` of [`{}`].",
+            FieldKey::new(idx, field),
+            self_id
+        );
+        tags.push(quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug, Default)]
+            pub struct #tag;
+        });
+    }
+    let doc = format!("Tags of the fields of [`{}`].", self_id);
+    ts.extend(quote! {
+        #[doc = #doc]
+        #[allow(non_snake_case, non_camel_case_types)]
+        #vis mod #tags_id {
+            #(#tags)*
+        }
+    });
+
+    let mut keys = HashMap::new();
+    for (idx, field) in fields.iter().enumerate() {
+        let name = FieldKey::new(idx, field).to_string();
+        if let Some(other) = keys.insert(name_key(&name), field) {
+            return Err(error_with_note(
+                field.span(),
+                format!(
+                    "the name key of field `{}` collides with the name key of another field.",
+                    name
+                ),
+                other.span(),
+                "the other field is here.",
+            ));
+        }
+        impl_field_by_tag(input, idx, field, ts);
+        impl_field_by_name(input, idx, field, &name, ts);
+        let key = type_key(&field.ty);
+        if fields.iter().filter(|f| type_key(&f.ty) == key).count() == 1 {
+            impl_field(input, idx, field, ts);
         }
-        ts.into()
+    }
+    Ok(())
+}
+fn tags_ident(input: &DeriveInput) -> Ident {
+    format_ident!("{}_fields", input.ident)
+}
+fn tag_ident(idx: usize, field: &Field) -> Ident {
+    if let Some(id) = &field.ident {
+        id.clone()
     } else {
-        panic!("`#[derive(Field)]` supports only struct.");
+        format_ident!("_{}", idx)
     }
 }
 
-fn impl_field_all(input: &DeriveInput, fields: &Punctuated<Field, Comma>, ts: &mut TokenStream) {
-    for (idx, field) in fields.iter().enumerate() {
-        impl_field(input, idx, field, ts);
+fn impl_field_by_name(
+    input: &DeriveInput,
+    idx: usize,
+    field: &Field,
+    name: &str,
+    ts: &mut TokenStream,
+) {
+    let self_id = &input.ident;
+    let (impl_g, self_g, impl_where) = input.generics.split_for_impl();
+
+    let key = name_key(name);
+    let tags_id = tags_ident(input);
+    let tag = tag_ident(idx, field);
+
+    let code = quote! {
+        impl #impl_g ::fieldmap::FieldByName<#key> for #self_id #self_g #impl_where {
+            type Tag = #tags_id::#tag;
+        }
+    };
+    ts.extend(code);
+}
+
+/// Same as `fieldmap::name_key`.
+fn name_key(name: &str) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for b in name.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
     }
+    hash
+}
+
+fn impl_field_by_tag(input: &DeriveInput, idx: usize, field: &Field, ts: &mut TokenStream) {
+    let self_id = &input.ident;
+    let (impl_g, self_g, impl_where) = input.generics.split_for_impl();
+
+    let id = to_member(idx, field);
+    let ty = &field.ty;
+    let tags_id = tags_ident(input);
+    let tag = tag_ident(idx, field);
+
+    let code = quote! {
+        impl #impl_g ::fieldmap::FieldByTag<#tags_id::#tag> for #self_id #self_g #impl_where {
+            type Type = #ty;
+
+            #[inline]
+            fn get(&self) -> &#ty {
+                &self.#id
+            }
+
+            #[inline]
+            fn get_mut(&mut self) -> &mut #ty {
+                &mut self.#id
+            }
+        }
+    };
+    ts.extend(code);
 }
 fn to_member(idx: usize, field: &Field) -> Member {
     if let Some(id) = &field.ident {
@@ -104,95 +226,1238 @@ fn impl_field(input: &DeriveInput, idx: usize, field: &Field, ts: &mut TokenStre
     ts.extend(code);
 }
 
+fn impl_try_field_all(input: &DeriveInput, data: &DataEnum, ts: &mut TokenStream) -> Result<()> {
+    let params = type_params(&input.generics);
+    // Like `Field<T>` for structs, `TryField<T>` is not implemented for types used by
+    // several fields of one variant, and the error is reported where it is used.
+    let mut ambiguous = HashSet::new();
+    for v in &data.variants {
+        for (idx, field) in v.fields.iter().enumerate() {
+            let key = type_key(&field.ty);
+            if v.fields.iter().take(idx).any(|f| type_key(&f.ty) == key) {
+                ambiguous.insert(key);
+            }
+        }
+    }
+    let mut fields: Vec<&Field> = Vec::new();
+    for v in &data.variants {
+        for field in &v.fields {
+            let key = type_key(&field.ty);
+            if ambiguous.contains(&key) {
+                continue;
+            }
+            for other in &fields {
+                check_conflict(other, field, &params)?;
+            }
+            if fields.iter().all(|f| type_key(&f.ty) != key) {
+                fields.push(field);
+            }
+        }
+    }
+    for field in fields {
+        impl_try_field(input, data, &field.ty, ts);
+    }
+    Ok(())
+}
+fn type_key(ty: &syn::Type) -> String {
+    quote!(#ty).to_string()
+}
+
+/// Reports fields whose `Field<T>` or `TryField<T>` implementations would conflict
+/// because their types differ but may be the same type after substituting type parameters.
+fn check_conflict(first: &Field, second: &Field, params: &HashSet<Ident>) -> Result<()> {
+    if type_key(&first.ty) != type_key(&second.ty) && may_unify(&first.ty, &second.ty, params) {
+        let (t0, t1) = (&first.ty, &second.ty);
+        return Err(error_with_note(
+            second.ty.span(),
+            format!(
+                "field type `{}` may be the same type as field type `{}`, so their implementations would conflict.",
+                quote!(#t1),
+                quote!(#t0)
+            ),
+            first.ty.span(),
+            "the other field type is here.",
+        ));
+    }
+    Ok(())
+}
+fn type_params(generics: &Generics) -> HashSet<Ident> {
+    generics.type_params().map(|p| p.ident.clone()).collect()
+}
+fn may_unify(a: &syn::Type, b: &syn::Type, params: &HashSet<Ident>) -> bool {
+    if is_type_param(a, params) || is_type_param(b, params) {
+        return true;
+    }
+    match (a, b) {
+        (syn::Type::Group(a), _) => may_unify(&a.elem, b, params),
+        (_, syn::Type::Group(b)) => may_unify(a, &b.elem, params),
+        (syn::Type::Paren(a), _) => may_unify(&a.elem, b, params),
+        (_, syn::Type::Paren(b)) => may_unify(a, &b.elem, params),
+        (syn::Type::Path(a), syn::Type::Path(b)) => {
+            if a.qself.is_some() || b.qself.is_some() {
+                return true;
+            }
+            let (a, b) = (&a.path.segments, &b.path.segments);
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.ident == b.ident && may_unify_args(&a.arguments, &b.arguments, params)
+                })
+        }
+        (syn::Type::Reference(a), syn::Type::Reference(b)) => {
+            a.mutability.is_some() == b.mutability.is_some() && may_unify(&a.elem, &b.elem, params)
+        }
+        (syn::Type::Ptr(a), syn::Type::Ptr(b)) => {
+            a.mutability.is_some() == b.mutability.is_some() && may_unify(&a.elem, &b.elem, params)
+        }
+        (syn::Type::Slice(a), syn::Type::Slice(b)) => may_unify(&a.elem, &b.elem, params),
+        (syn::Type::Array(a), syn::Type::Array(b)) => may_unify(&a.elem, &b.elem, params),
+        (syn::Type::Tuple(a), syn::Type::Tuple(b)) => {
+            a.elems.len() == b.elems.len()
+                && a.elems
+                    .iter()
+                    .zip(&b.elems)
+                    .all(|(a, b)| may_unify(a, b, params))
+        }
+        (syn::Type::Path(_), _)
+        | (_, syn::Type::Path(_))
+        | (syn::Type::Reference(_), _)
+        | (syn::Type::Ptr(_), _)
+        | (syn::Type::Slice(_), _)
+        | (syn::Type::Array(_), _)
+        | (syn::Type::Tuple(_), _) => false,
+        _ => true,
+    }
+}
+fn may_unify_args(a: &PathArguments, b: &PathArguments, params: &HashSet<Ident>) -> bool {
+    match (a, b) {
+        (PathArguments::None, PathArguments::None) => true,
+        (PathArguments::AngleBracketed(a), PathArguments::AngleBracketed(b)) => {
+            a.args.len() == b.args.len()
+                && a.args.iter().zip(&b.args).all(|(a, b)| match (a, b) {
+                    (GenericArgument::Type(a), GenericArgument::Type(b)) => may_unify(a, b, params),
+                    _ => true,
+                })
+        }
+        _ => true,
+    }
+}
+fn is_type_param(ty: &syn::Type, params: &HashSet<Ident>) -> bool {
+    if let syn::Type::Path(p) = ty {
+        if p.qself.is_none() {
+            if let Some(id) = p.path.get_ident() {
+                return params.contains(id);
+            }
+        }
+    }
+    false
+}
+
+fn impl_try_field(input: &DeriveInput, data: &DataEnum, ty: &syn::Type, ts: &mut TokenStream) {
+    let self_id = &input.ident;
+    let (impl_g, self_g, impl_where) = input.generics.split_for_impl();
+
+    let key = type_key(ty);
+    let mut arms_get = Vec::new();
+    let mut arms_get_mut = Vec::new();
+    for v in &data.variants {
+        let v_id = &v.ident;
+        let mut members = Vec::new();
+        for (idx, field) in v.fields.iter().enumerate() {
+            if type_key(&field.ty) == key {
+                members.push(to_member(idx, field));
+            }
+        }
+        match members.len() {
+            0 => {
+                arms_get.push(quote!(Self::#v_id { .. } => ::core::option::Option::None));
+                arms_get_mut.push(quote!(Self::#v_id { .. } => ::core::option::Option::None));
+            }
+            1 => {
+                let m = &members[0];
+                arms_get
+                    .push(quote!(Self::#v_id { #m: ref v, .. } => ::core::option::Option::Some(v)));
+                arms_get_mut.push(
+                    quote!(Self::#v_id { #m: ref mut v, .. } => ::core::option::Option::Some(v)),
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let code = quote! {
+        impl #impl_g ::fieldmap::TryField<#ty> for #self_id #self_g #impl_where {
+            #[inline]
+            fn try_get(&self) -> ::core::option::Option<&#ty> {
+                match *self {
+                    #(#arms_get,)*
+                }
+            }
+
+            #[inline]
+            fn try_get_mut(&mut self) -> ::core::option::Option<&mut #ty> {
+                match *self {
+                    #(#arms_get_mut,)*
+                }
+            }
+        }
+    };
+    ts.extend(code);
+}
+
 #[derive(StructMeta)]
 struct FieldsArgs {
-    item: Expr,
+    item: Option<Expr>,
+    rename_all: Option<LitStr>,
+    partial: Option<LitStr>,
 }
 
-fn get_item_trait(attrs: &[syn::Attribute]) -> Result<Option<Path>> {
+struct ContainerArgs {
+    item_ids: Vec<Path>,
+    rename_all: Option<RenameRule>,
+    partial: Option<(Ident, Span)>,
+}
+impl ContainerArgs {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut item_ids = Vec::new();
+        let mut rename_all = None;
+        let mut partial = None;
+        for attr in attrs {
+            if attr.path.is_ident("fields") {
+                let args: FieldsArgs = attr.parse_args()?;
+                match args.item {
+                    Some(Expr::Path(path)) => item_ids.push(path.path),
+                    Some(Expr::Lit(ExprLit {
+                        lit: Lit::Str(s), ..
+                    })) => item_ids.push(syn::parse_str::<Path>(&s.value())?),
+                    Some(_) => bail!(
+                        attr.span(),
+                        "item parameter must specify string literal or path."
+                    ),
+                    None => {}
+                }
+                if let Some(lit) = &args.rename_all {
+                    if rename_all.is_some() {
+                        bail!(
+                            lit.span(),
+                            "parameter `rename_all` specified more than once"
+                        );
+                    }
+                    rename_all = Some(RenameRule::from_lit(lit)?);
+                }
+                if let Some(lit) = &args.partial {
+                    if partial.is_some() {
+                        bail!(lit.span(), "parameter `partial` specified more than once");
+                    }
+                    let ident = match syn::parse_str::<Ident>(&lit.value()) {
+                        Ok(ident) => Ident::new(&ident.to_string(), lit.span()),
+                        Err(_) => bail!(lit.span(), "`partial` must be an identifier."),
+                    };
+                    partial = Some((ident, lit.span()));
+                }
+            }
+        }
+        Ok(Self {
+            item_ids,
+            rename_all,
+            partial,
+        })
+    }
+}
+
+#[derive(StructMeta)]
+struct FieldArgs {
+    skip: bool,
+    rename: Option<LitStr>,
+    alias: Option<LitStr>,
+    meta: Option<NameArgs<Vec<MetaNameValue>>>,
+    flatten: Flag,
+    prefix: Option<LitStr>,
+    nested: bool,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    rename: Option<LitStr>,
+    aliases: Vec<LitStr>,
+    meta: Vec<(String, LitStr)>,
+    flatten: Option<Span>,
+    prefix: Option<LitStr>,
+    nested: bool,
+}
+impl FieldAttrs {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut this = FieldAttrs::default();
+        for attr in attrs {
+            if attr.path.is_ident("fields") {
+                let args: FieldArgs = attr.parse_args()?;
+                this.skip |= args.skip;
+                this.nested |= args.nested;
+                if let Some(rename) = args.rename {
+                    if this.rename.is_some() {
+                        bail!(rename.span(), "parameter `rename` specified more than once");
+                    }
+                    this.rename = Some(rename);
+                }
+                this.aliases.extend(args.alias);
+                if let Some(span) = args.flatten.span {
+                    this.flatten = Some(span);
+                }
+                if let Some(prefix) = args.prefix {
+                    if this.prefix.is_some() {
+                        bail!(prefix.span(), "parameter `prefix` specified more than once");
+                    }
+                    this.prefix = Some(prefix);
+                }
+                for m in args.meta.into_iter().flat_map(|m| m.args) {
+                    let key = match m.path.get_ident() {
+                        Some(id) => trim_raw(&id.to_string()).to_string(),
+                        None => bail!(m.path.span(), "meta key must be an identifier."),
+                    };
+                    let value = match m.lit {
+                        Lit::Str(value) => value,
+                        lit => bail!(lit.span(), "meta value must be a string literal."),
+                    };
+                    if this.meta.iter().any(|(k, _)| k == &key) {
+                        bail!(m.path.span(), "duplicate meta key `{}`.", key);
+                    }
+                    this.meta.push((key, value));
+                }
+            }
+        }
+        if let Some(span) = this.flatten {
+            if this.rename.is_some() || !this.aliases.is_empty() {
+                bail!(span, "`flatten` cannot be used with `rename` or `alias`.");
+            }
+            if this.nested {
+                bail!(span, "`flatten` cannot be used with `nested`.");
+            }
+        } else if let Some(prefix) = &this.prefix {
+            bail!(prefix.span(), "`prefix` can be used only with `flatten`.");
+        }
+        Ok(this)
+    }
+}
+
+struct FieldEntry {
+    member: Member,
+    name: String,
+    aliases: Vec<LitStr>,
+    ty: String,
+    doc: String,
+    vis: String,
+    meta: Vec<(String, LitStr)>,
+    /// The type and the name prefix of a field with `#[fields(flatten)]`.
+    flatten: Option<(syn::Type, String)>,
+    nested: bool,
+    span: Span,
+}
+
+fn field_entries(fields: &Fields, args: &ContainerArgs) -> Result<Vec<FieldEntry>> {
+    let mut entries = Vec::new();
+    let mut names = HashSet::new();
+    let mut loose_names = HashMap::new();
+    for (pos, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        let key = FieldKey::new(pos, field);
+        if attrs.flatten.is_some() {
+            let prefix = attrs.prefix.map(|p| p.value()).unwrap_or_default();
+            entries.push(FieldEntry {
+                member: key.to_member(),
+                name: String::new(),
+                aliases: Vec::new(),
+                ty: type_string(&field.ty),
+                doc: doc_string(&field.attrs),
+                vis: vis_string(&field.vis),
+                meta: attrs.meta,
+                flatten: Some((field.ty.clone(), prefix)),
+                nested: false,
+                span: field.span(),
+            });
+            continue;
+        }
+        let name = key.to_name(&attrs, args.rename_all);
+        if !names.insert(name.clone()) {
+            bail!(field.span(), "duplicate field name `{}`.", name);
+        }
+        for alias in &attrs.aliases {
+            if !names.insert(alias.value()) {
+                bail!(alias.span(), "duplicate field name `{}`.", alias.value());
+            }
+        }
+        let name_span = attrs.rename.as_ref().map_or(field.span(), |r| r.span());
+        let spans = std::iter::once((name.clone(), name_span));
+        for (name, span) in spans.chain(attrs.aliases.iter().map(|a| (a.value(), a.span()))) {
+            let value = (name.clone(), span, entries.len());
+            if let Some((other, other_span, other_idx)) =
+                loose_names.insert(loose_name(&name), value)
+            {
+                if other_idx == entries.len() {
+                    continue;
+                }
+                return Err(error_with_note(
+                    span,
+                    format!(
+                        "field name `{}` is the same as `{}` when case, `_` and `-` are ignored.",
+                        name, other
+                    ),
+                    other_span,
+                    "the other field name is here.",
+                ));
+            }
+        }
+        entries.push(FieldEntry {
+            member: key.to_member(),
+            name,
+            aliases: attrs.aliases,
+            ty: type_string(&field.ty),
+            doc: doc_string(&field.attrs),
+            vis: vis_string(&field.vis),
+            meta: attrs.meta,
+            flatten: None,
+            nested: attrs.nested,
+            span: field.span(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Same as `fieldmap::__private::normalize_name`.
+fn loose_name(name: &str) -> String {
+    name.chars()
+        .filter(|&c| c != '_' && c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+/// Pattern that matches the normalized name and aliases of `e`. Updates `max_len` to the longest one.
+fn find_loose_pat(e: &FieldEntry, max_len: &mut usize) -> TokenStream {
+    let mut names = vec![loose_name(&e.name)];
+    for alias in &e.aliases {
+        let alias = loose_name(&alias.value());
+        if !names.contains(&alias) {
+            names.push(alias);
+        }
+    }
+    let mut pats = Vec::new();
+    for name in names {
+        *max_len = (*max_len).max(name.len());
+        let bytes = name.bytes().map(Literal::u8_suffixed);
+        pats.push(quote!([#(#bytes),*]));
+    }
+    quote!(#(#pats)|*)
+}
+fn find_loose_body(entries: &[FieldEntry]) -> TokenStream {
+    let mut max_len = 0;
+    let mut arms = Vec::new();
+    for (idx, e) in entries.iter().enumerate() {
+        let pat = find_loose_pat(e, &mut max_len);
+        arms.push(quote!(::core::option::Option::Some(#pat) => ::core::option::Option::Some(#idx)));
+    }
+    quote! {
+        let mut buf = [0u8; #max_len];
+        match ::fieldmap::__private::normalize_name(name, &mut buf) {
+            #(#arms,)*
+            _ => ::core::option::Option::None,
+        }
+    }
+}
+/// Distributes the fields bound to `_0`, `_1`, ... to the positions in `idx` without overlapping borrows.
+fn get_many_mut_body(entries: &[FieldEntry]) -> TokenStream {
+    let len = entries.len();
+    let fill = (0..len).map(|idx| {
+        let b = format_ident!("_{}", idx);
+        quote! {
+            if let ::core::option::Option::Some(p) = idx.iter().position(|&i| i == #idx) {
+                items[p] = ::core::option::Option::Some(#b);
+            }
+        }
+    });
+    quote! {
+        ::fieldmap::__private::check_disjoint(&idx, #len)?;
+        let mut items: [::core::option::Option<&mut Self::Item<'_>>; __N] =
+            ::core::array::from_fn(|_| ::core::option::Option::None);
+        #(#fill)*
+        ::core::result::Result::Ok(items.map(::core::option::Option::unwrap))
+    }
+}
+
+/// `Fields::get_many_mut` of a struct, which splits the borrow with `FieldsAs::split_mut_as`.
+fn get_many_mut_split_body() -> TokenStream {
+    quote! {
+        ::fieldmap::__private::check_disjoint(&idx, <Self as ::fieldmap::Fields>::LEN)?;
+        let mut items: [::core::option::Option<&mut Self::Item<'_>>; __N] =
+            ::core::array::from_fn(|_| ::core::option::Option::None);
+        ::fieldmap::FieldsAs::split_mut_as(self, &idx, 0, &mut items[..])?;
+        if let ::core::option::Option::Some(position) =
+            items.iter().position(::core::option::Option::is_none)
+        {
+            return ::core::result::Result::Err(::fieldmap::DisjointError::NotFound { position });
+        }
+        ::core::result::Result::Ok(items.map(::core::option::Option::unwrap))
+    }
+}
+
+/// `FieldsAs::split_mut_as` of a struct whose fields are bound to `_0`, `_1`, ... by `binds_mut`.
+fn split_mut_as_body(binds_mut: &[TokenStream], split: &[TokenStream]) -> TokenStream {
+    if split.is_empty() {
+        return TokenStream::new();
+    }
+    quote! {
+        let Self { #(#binds_mut,)* .. } = *self;
+        #(#split)*
+        ::core::result::Result::Ok(())
+    }
+}
+
+/// Statement of `FieldsAs::split_mut_as` that stores the field bound to `b` at index `offset + idx`.
+fn split_mut_as_stmt(b: &Ident, idx: TokenStream) -> TokenStream {
+    quote! {
+        if let ::core::option::Option::Some(p) = idx.iter().position(|&i| i == offset + #idx) {
+            items[p] = ::core::option::Option::Some(#b);
+        }
+    }
+}
+
+fn arms_name(entries: &[FieldEntry]) -> Vec<TokenStream> {
+    let mut arms = Vec::new();
+    for (idx, e) in entries.iter().enumerate() {
+        let name = &e.name;
+        arms.push(quote!(#idx => ::core::option::Option::Some(#name)));
+    }
+    arms
+}
+/// Statement that passes the field `value` of `e` named `name` to `visitor`, or traverses it if it is `#[fields(nested)]`.
+fn visit_stmt(e: &FieldEntry, name: TokenStream, value: TokenStream) -> TokenStream {
+    let f = if e.nested {
+        quote!(::fieldmap::Visit::visit_with(#value, ::core::option::Option::Some(&path), visitor))
+    } else {
+        quote!(visitor.leaf(&path, #value))
+    };
+    quote! {
+        {
+            let path = ::fieldmap::Path::new(path, ::fieldmap::Segment::Name(#name));
+            #f;
+        }
+    }
+}
+/// `Visit::visit_with` of a struct, which visits the fields with `Visit::visit_fields`.
+fn visit_struct_body() -> TokenStream {
+    quote! {
+        visitor.enter_struct(path);
+        ::fieldmap::Visit::visit_fields(self, path, <Self as ::fieldmap::Fields>::FIELD_NAMES, visitor);
+        visitor.leave_struct(path);
+    }
+}
+fn info_expr(e: &FieldEntry) -> TokenStream {
+    let FieldEntry {
+        name,
+        ty,
+        doc,
+        vis,
+        meta,
+        ..
+    } = e;
+    let meta_keys = meta.iter().map(|(k, _)| k);
+    let meta_values = meta.iter().map(|(_, v)| v);
+    quote! {
+        ::core::option::Option::Some(&::fieldmap::FieldInfo {
+            name: #name,
+            ty: #ty,
+            doc: #doc,
+            vis: #vis,
+            meta: &[#((#meta_keys, #meta_values),)*],
+        })
+    }
+}
+fn arms_info(entries: &[FieldEntry]) -> Vec<TokenStream> {
+    let mut arms = Vec::new();
+    for (idx, e) in entries.iter().enumerate() {
+        let info = info_expr(e);
+        arms.push(quote!(#idx => #info));
+    }
+    arms
+}
+/// Arms that match `name.as_bytes()` against the bytes of each name.
+///
+/// Unlike `&str` patterns, which are compared one by one, slice patterns are compiled into
+/// a decision tree on the length and then on each byte, so lookup does not slow down as fields increase.
+fn arms_find(entries: &[FieldEntry]) -> Vec<TokenStream> {
+    let mut arms = Vec::new();
+    for (idx, e) in entries.iter().enumerate() {
+        let pat = find_pat(e);
+        arms.push(quote!(#pat => ::core::option::Option::Some(#idx)));
+    }
+    arms
+}
+fn find_pat(e: &FieldEntry) -> TokenStream {
+    let names = std::iter::once(e.name.clone()).chain(e.aliases.iter().map(|a| a.value()));
+    let pats = names.map(|name| {
+        let bytes = name.bytes().map(Literal::u8_suffixed);
+        quote!([#(#bytes),*])
+    });
+    quote!(#(#pats)|*)
+}
+
+/// Returns the doc comment in `attrs` with the leading space of each line removed.
+fn doc_string(attrs: &[syn::Attribute]) -> String {
+    let mut lines = Vec::new();
     for attr in attrs {
-        if attr.path.is_ident("fields") {
-            let args: FieldsArgs = attr.parse_args()?;
-            match args.item {
-                Expr::Path(path) => return Ok(Some(path.path)),
-                Expr::Lit(ExprLit {
-                    lit: Lit::Str(s), ..
-                }) => return Ok(Some(syn::parse_str::<Path>(&s.value())?)),
-                _ => bail!(
-                    attr.span(),
-                    "item parameter must specify string literal or path."
-                ),
+        if let Ok(Meta::NameValue(MetaNameValue {
+            path,
+            lit: Lit::Str(s),
+            ..
+        })) = attr.parse_meta()
+        {
+            if path.is_ident("doc") {
+                let line = s.value();
+                lines.push(line.strip_prefix(' ').unwrap_or(&line).to_string());
             }
         }
     }
-    Ok(None)
+    lines.join("\n")
 }
-fn impl_field_map(
+/// Returns `ty` as written in the source, without the spaces `to_string` inserts between tokens.
+fn type_string(ty: &syn::Type) -> String {
+    fn write(s: &mut String, tokens: TokenStream) {
+        let mut prev_word = false;
+        for t in tokens {
+            match t {
+                TokenTree::Ident(_) | TokenTree::Literal(_) => {
+                    if prev_word {
+                        s.push(' ');
+                    }
+                    s.push_str(&t.to_string());
+                    prev_word = true;
+                    continue;
+                }
+                TokenTree::Punct(p) => match p.as_char() {
+                    ',' | ';' => {
+                        s.push(p.as_char());
+                        s.push(' ');
+                    }
+                    '+' | '=' => {
+                        s.push(' ');
+                        s.push(p.as_char());
+                        s.push(' ');
+                    }
+                    '-' => s.push_str(" -"),
+                    '>' if s.ends_with('-') => s.push_str("> "),
+                    c => s.push(c),
+                },
+                TokenTree::Group(g) => {
+                    let (open, close) = match g.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::None => ("", ""),
+                    };
+                    let keyword = ["mut", "dyn", "impl", "const"]
+                        .iter()
+                        .any(|k| s.ends_with(k));
+                    if prev_word && (g.delimiter() != Delimiter::Parenthesis || keyword) {
+                        s.push(' ');
+                    }
+                    s.push_str(open);
+                    write(s, g.stream());
+                    if s.ends_with(' ') {
+                        s.pop();
+                    }
+                    s.push_str(close);
+                }
+            }
+            prev_word = false;
+        }
+    }
+    let mut s = String::new();
+    write(&mut s, ty.to_token_stream());
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+fn vis_string(vis: &Visibility) -> String {
+    match vis {
+        Visibility::Public(_) => "pub".into(),
+        Visibility::Crate(_) => "crate".into(),
+        Visibility::Restricted(r) => {
+            let path: Vec<_> = r
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let path = path.join("::");
+            if r.in_token.is_some() {
+                format!("pub(in {})", path)
+            } else {
+                format!("pub({})", path)
+            }
+        }
+        Visibility::Inherited => String::new(),
+    }
+}
+
+/// Bodies of the methods that differ between struct and enum.
+struct FieldMapBodies {
+    field_names: TokenStream,
+    find: TokenStream,
+    find_loose: TokenStream,
+    info: TokenStream,
+    get: TokenStream,
+    get_mut: TokenStream,
+    visit: TokenStream,
+    /// `Visit::visit_fields`, or empty to use the default implementation.
+    visit_fields: TokenStream,
+    /// `Fields::get_many_mut`, or empty to use the default implementation.
+    get_many_mut: TokenStream,
+    /// `FieldsAs::split_mut_as`, or empty to use the default implementation.
+    split_mut_as: TokenStream,
+    instance: TokenStream,
+}
+
+fn impl_field_map_struct(
     input: &DeriveInput,
-    item_id: &Path,
-    fields: &Punctuated<Field, Comma>,
+    args: &ContainerArgs,
+    fields: &Fields,
     ts: &mut TokenStream,
-) {
-    let self_id = &input.ident;
-    let (impl_g, self_g, impl_where) = input.generics.split_for_impl();
-    let impl_gps = &input.generics.params;
-
+) -> Result<()> {
+    let entries = field_entries(fields, args)?;
+    if entries.iter().any(|e| e.flatten.is_some()) {
+        return impl_field_map_flatten(input, args, &entries, ts);
+    }
     let mut arms_get = Vec::new();
     let mut arms_get_mut = Vec::new();
-    let mut arms_name = Vec::new();
+    let mut visit = Vec::new();
+    let mut binds_mut = Vec::new();
+    let mut split = Vec::new();
+    for (idx, e) in entries.iter().enumerate() {
+        let m = &e.member;
+        let b = format_ident!("_{}", idx);
+        binds_mut.push(quote!(#m: ref mut #b));
+        split.push(split_mut_as_stmt(&b, quote!(#idx)));
+        arms_get.push(quote!(#idx => ::core::option::Option::Some(&self.#m)));
+        arms_get_mut.push(quote!(#idx => ::core::option::Option::Some(&mut self.#m)));
+        visit.push(visit_stmt(e, quote!(names[#idx]), quote!(&self.#m)));
+    }
+    let arms_find = arms_find(&entries);
+    let arms_info = arms_info(&entries);
+    let bodies = FieldMapBodies {
+        field_names: {
+            let names = entries.iter().map(|e| &e.name);
+            quote!(&[#(#names),*])
+        },
+        find: quote! {
+            match name.as_bytes() {
+                #(#arms_find,)*
+                _ => ::core::option::Option::None,
+            }
+        },
+        find_loose: find_loose_body(&entries),
+        info: quote! {
+            match idx {
+                #(#arms_info,)*
+                _ => ::core::option::Option::None,
+            }
+        },
+        get: quote! {
+            match idx {
+                #(#arms_get,)*
+                _ => ::core::option::Option::None,
+            }
+        },
+        get_mut: quote! {
+            match idx {
+                #(#arms_get_mut,)*
+                _ => ::core::option::Option::None,
+            }
+        },
+        visit: visit_struct_body(),
+        visit_fields: quote! {
+            #(#visit)*
+        },
+        get_many_mut: get_many_mut_split_body(),
+        split_mut_as: split_mut_as_body(&binds_mut, &split),
+        instance: TokenStream::new(),
+    };
+    impl_field_map(input, args, &bodies, ts);
+    Ok(())
+}
+
+/// Implements `Fields` for a struct with `#[fields(flatten)]` fields.
+///
+/// Indices of the fields after a flattened field depend on `Fields::LEN` of its type,
+/// so the methods walk the fields in order instead of matching on literal indices.
+fn impl_field_map_flatten(
+    input: &DeriveInput,
+    args: &ContainerArgs,
+    entries: &[FieldEntry],
+    ts: &mut TokenStream,
+) -> Result<()> {
+    let mut groups = Vec::new();
+    let mut names = Vec::new();
     let mut arms_find = Vec::new();
-    for (idx, field) in fields.iter().enumerate() {
-        let key = FieldKey::new(idx, field);
-        let m = key.to_member();
-        arms_get.push(quote!(#idx => Some(&self.#m)));
-        arms_get_mut.push(quote!(#idx => Some(&mut self.#m)));
+    let mut arms_find_loose = Vec::new();
+    let mut find_flatten = Vec::new();
+    let mut find_loose_flatten = Vec::new();
+    let mut max_len = 0;
+    let mut info = Vec::new();
+    let mut get = Vec::new();
+    let mut get_mut = Vec::new();
+    let mut visit = Vec::new();
+    let mut binds_mut = Vec::new();
+    let mut split = Vec::new();
+    let mut offset = quote!(0usize);
+    for (i, e) in entries.iter().enumerate() {
+        let m = &e.member;
+        let b = format_ident!("_{}", i);
+        binds_mut.push(quote!(#m: ref mut #b));
+        let len;
+        if let Some((ty, prefix)) = &e.flatten {
+            if uses_generics(ty, &input.generics) {
+                bail!(
+                    ty.span(),
+                    "`#[fields(flatten)]` does not support types that use generic parameters."
+                );
+            }
+            if !names.is_empty() {
+                groups.push(quote!(("", &[#(#names),*])));
+                names.clear();
+            }
+            groups.push(quote!((#prefix, <#ty as ::fieldmap::Fields>::FIELD_NAMES)));
+            len = quote!(<#ty as ::fieldmap::Fields>::LEN);
+            find_flatten.push(quote! {
+                if let ::core::option::Option::Some(name) = name.strip_prefix(#prefix) {
+                    if let ::core::option::Option::Some(idx) = <#ty as ::fieldmap::Fields>::find(name) {
+                        return ::core::option::Option::Some(#offset + idx);
+                    }
+                }
+            });
+            let loose_prefix = loose_name(prefix);
+            find_loose_flatten.push(quote! {
+                if let ::core::option::Option::Some(name) = ::fieldmap::__private::strip_prefix_loose(name, #loose_prefix) {
+                    if let ::core::option::Option::Some(idx) = <#ty as ::fieldmap::Fields>::find_loose(name) {
+                        return ::core::option::Option::Some(#offset + idx);
+                    }
+                }
+            });
+            info.push(quote! {
+                if idx < #len {
+                    return <#ty as ::fieldmap::Fields>::info(idx);
+                }
+            });
+            get.push(quote! {
+                if idx < #len {
+                    return ::fieldmap::FieldsAs::get_as(&self.#m, idx);
+                }
+            });
+            get_mut.push(quote! {
+                if idx < #len {
+                    return ::fieldmap::FieldsAs::get_mut_as(&mut self.#m, idx);
+                }
+            });
+            visit.push(quote! {
+                ::fieldmap::Visit::visit_fields(&self.#m, path, &names[#offset..#offset + #len], visitor);
+            });
+            split.push(quote! {
+                ::fieldmap::FieldsAs::split_mut_as(#b, idx, offset + #offset, items)?;
+            });
+        } else {
+            names.push(&e.name);
+            len = quote!(1);
+            let pat = find_pat(e);
+            arms_find.push(quote!(#pat => return ::core::option::Option::Some(#offset)));
+            let pat = find_loose_pat(e, &mut max_len);
+            arms_find_loose
+                .push(quote!(::core::option::Option::Some(#pat) => return ::core::option::Option::Some(#offset)));
+            let info_expr = info_expr(e);
+            info.push(quote! {
+                if idx == 0 {
+                    return #info_expr;
+                }
+            });
+            get.push(quote! {
+                if idx == 0 {
+                    return ::core::option::Option::Some(&self.#m);
+                }
+            });
+            get_mut.push(quote! {
+                if idx == 0 {
+                    return ::core::option::Option::Some(&mut self.#m);
+                }
+            });
+            visit.push(visit_stmt(e, quote!(names[#offset]), quote!(&self.#m)));
+            split.push(split_mut_as_stmt(&b, offset.clone()));
+        }
+        if i + 1 < entries.len() {
+            let next = quote!(let idx = idx - #len;);
+            info.push(next.clone());
+            get.push(next.clone());
+            get_mut.push(next);
+        }
+        offset = quote!(#offset + #len);
+    }
+    if !names.is_empty() {
+        groups.push(quote!(("", &[#(#names),*])));
+    }
+    let find_own = if arms_find.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            match name.as_bytes() {
+                #(#arms_find,)*
+                _ => {}
+            }
+        }
+    };
+    let find_loose_own = if arms_find_loose.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            let mut buf = [0u8; #max_len];
+            match ::fieldmap::__private::normalize_name(name, &mut buf) {
+                #(#arms_find_loose,)*
+                _ => {}
+            }
+        }
+    };
+    let bodies = FieldMapBodies {
+        field_names: quote! {
+            {
+                const GROUPS: &[::fieldmap::__private::NameGroup] = &[#(#groups),*];
+                const BYTES: [u8; ::fieldmap::__private::names_bytes_len(GROUPS)] =
+                    ::fieldmap::__private::names_bytes(GROUPS);
+                const NAMES: [&str; ::fieldmap::__private::names_len(GROUPS)] =
+                    ::fieldmap::__private::names(&BYTES, GROUPS);
+                &NAMES
+            }
+        },
+        find: quote! {
+            #find_own
+            #(#find_flatten)*
+            ::core::option::Option::None
+        },
+        find_loose: quote! {
+            #find_loose_own
+            #(#find_loose_flatten)*
+            ::core::option::Option::None
+        },
+        info: quote! {
+            #(#info)*
+            ::core::option::Option::None
+        },
+        get: quote! {
+            #(#get)*
+            ::core::option::Option::None
+        },
+        get_mut: quote! {
+            #(#get_mut)*
+            ::core::option::Option::None
+        },
+        visit: visit_struct_body(),
+        visit_fields: quote! {
+            #(#visit)*
+        },
+        get_many_mut: get_many_mut_split_body(),
+        split_mut_as: split_mut_as_body(&binds_mut, &split),
+        instance: TokenStream::new(),
+    };
+    impl_field_map(input, args, &bodies, ts);
+    if input.generics.params.is_empty() {
+        // Check for duplicate names even if `FIELD_NAMES` is not used.
+        let self_id = &input.ident;
+        ts.extend(quote! {
+            const _: &[&str] = <#self_id as ::fieldmap::Fields>::FIELD_NAMES;
+        });
+    }
+    Ok(())
+}
 
-        let s = key.to_string();
-        arms_name.push(quote!(#idx => Some(#s)));
-        arms_find.push(quote!(#s => Some(#idx)));
+/// Returns true if `ty` uses `Self` or a generic parameter of `generics`.
+fn uses_generics(ty: &syn::Type, generics: &Generics) -> bool {
+    fn walk(tokens: TokenStream, names: &HashSet<String>) -> bool {
+        let mut lifetime = false;
+        for t in tokens {
+            let quote = matches!(&t, TokenTree::Punct(p) if p.as_char() == '\'');
+            match t {
+                TokenTree::Ident(id) => {
+                    let id = if lifetime {
+                        format!("'{}", id)
+                    } else {
+                        id.to_string()
+                    };
+                    if id == "Self" || names.contains(&id) {
+                        return true;
+                    }
+                }
+                TokenTree::Group(g) if walk(g.stream(), names) => return true,
+                _ => {}
+            }
+            lifetime = quote;
+        }
+        false
+    }
+    let mut names = HashSet::new();
+    for p in &generics.params {
+        names.insert(match p {
+            GenericParam::Type(p) => p.ident.to_string(),
+            GenericParam::Lifetime(p) => p.lifetime.to_string(),
+            GenericParam::Const(p) => p.ident.to_string(),
+        });
     }
+    walk(ty.to_token_stream(), &names)
+}
 
-    let len = fields.len();
-    let code = quote_spanned! { item_id.span() =>
-        impl #impl_g ::fieldmap::Fields for #self_id #self_g #impl_where {
-            type Item = dyn #item_id;
+fn impl_field_map_enum(
+    input: &DeriveInput,
+    args: &ContainerArgs,
+    data: &DataEnum,
+    ts: &mut TokenStream,
+) -> Result<()> {
+    let mut arms_get = Vec::new();
+    let mut arms_get_mut = Vec::new();
+    let mut arms_field_len = Vec::new();
+    let mut arms_field_find = Vec::new();
+    let mut arms_field_find_loose = Vec::new();
+    let mut arms_field_name = Vec::new();
+    let mut arms_field_info = Vec::new();
+    let mut arms_variant_name = Vec::new();
+    let mut arms_visit = Vec::new();
+    let mut arms_get_many_mut = Vec::new();
+    for v in &data.variants {
+        let entries = field_entries(&v.fields, args)?;
+        if let Some(e) = entries.iter().find(|e| e.flatten.is_some()) {
+            bail!(e.span, "`#[fields(flatten)]` is not supported for enums.");
+        }
+        let v_id = &v.ident;
+        let v_name = trim_raw(&v_id.to_string()).to_string();
+        let mut binds = Vec::new();
+        let mut binds_mut = Vec::new();
+        let mut arms_get_v = Vec::new();
+        let mut visit = Vec::new();
+        for (idx, e) in entries.iter().enumerate() {
+            let m = &e.member;
+            let b = format_ident!("_{}", idx);
+            binds.push(quote!(#m: ref #b));
+            binds_mut.push(quote!(#m: ref mut #b));
+            arms_get_v.push(quote!(#idx => ::core::option::Option::Some(#b)));
+            let name = &e.name;
+            visit.push(visit_stmt(e, quote!(#name), quote!(#b)));
+        }
+        arms_visit.push(quote! {
+            Self::#v_id { #(#binds,)* .. } => {
+                #(#visit)*
+                visitor.leave_struct(path);
+            }
+        });
+        arms_get.push(quote! {
+            Self::#v_id { #(#binds,)* .. } => match idx {
+                #(#arms_get_v,)*
+                _ => ::core::option::Option::None,
+            }
+        });
+        arms_get_mut.push(quote! {
+            Self::#v_id { #(#binds_mut,)* .. } => match idx {
+                #(#arms_get_v,)*
+                _ => ::core::option::Option::None,
+            }
+        });
+        let get_many_mut = get_many_mut_body(&entries);
+        arms_get_many_mut.push(quote! {
+            Self::#v_id { #(#binds_mut,)* .. } => {
+                #get_many_mut
+            }
+        });
 
+        let len = entries.len();
+        let arms_find = arms_find(&entries);
+        let arms_name = arms_name(&entries);
+        let arms_info = arms_info(&entries);
+        arms_field_len.push(quote!(Self::#v_id { .. } => #len));
+        arms_field_find.push(quote! {
+            Self::#v_id { .. } => match name.as_bytes() {
+                #(#arms_find,)*
+                _ => ::core::option::Option::None,
+            }
+        });
+        let find_loose = find_loose_body(&entries);
+        arms_field_find_loose.push(quote! {
+            Self::#v_id { .. } => {
+                #find_loose
+            }
+        });
+        arms_field_name.push(quote! {
+            Self::#v_id { .. } => match idx {
+                #(#arms_name,)*
+                _ => ::core::option::Option::None,
+            }
+        });
+        arms_field_info.push(quote! {
+            Self::#v_id { .. } => match idx {
+                #(#arms_info,)*
+                _ => ::core::option::Option::None,
+            }
+        });
+        arms_variant_name.push(quote!(Self::#v_id { .. } => ::core::option::Option::Some(#v_name)));
+    }
+    // Keep arguments used even when the enum has no variants.
+    let use_idx = quote!(let _ = idx;);
+    let use_name = quote!(let _ = name;);
+    let bodies = FieldMapBodies {
+        field_names: quote!(&[]),
+        find: quote! {
+            #use_name
+            ::core::option::Option::None
+        },
+        find_loose: quote! {
+            #use_name
+            ::core::option::Option::None
+        },
+        info: quote! {
+            #use_idx
+            ::core::option::Option::None
+        },
+        get: quote! {
+            #use_idx
+            match *self {
+                #(#arms_get,)*
+            }
+        },
+        get_mut: quote! {
+            #use_idx
+            match *self {
+                #(#arms_get_mut,)*
+            }
+        },
+        visit: quote! {
+            visitor.enter_struct(path);
+            match *self {
+                #(#arms_visit,)*
+            }
+        },
+        visit_fields: TokenStream::new(),
+        get_many_mut: quote! {
+            let _ = idx;
+            match *self {
+                #(#arms_get_many_mut,)*
+            }
+        },
+        split_mut_as: TokenStream::new(),
+        instance: quote! {
             #[inline]
-            fn len() -> usize {
-               #len
+            fn field_len(&self) -> usize {
+                match *self {
+                    #(#arms_field_len,)*
+                }
             }
             #[inline]
-            fn find(name: &str) -> Option<usize> {
-                match name {
-                    #(#arms_find,)*
-                    _ => None,
+            fn field_find(&self, name: &str) -> ::core::option::Option<usize> {
+                #use_name
+                match *self {
+                    #(#arms_field_find,)*
                 }
             }
             #[inline]
-            fn name(idx: usize) -> Option<&'static str> {
-                match idx {
-                    #(#arms_name,)*
-                    _ => None,
+            fn field_find_loose(&self, name: &str) -> ::core::option::Option<usize> {
+                #use_name
+                match *self {
+                    #(#arms_field_find_loose,)*
                 }
             }
             #[inline]
-            fn get(&self, idx: usize) -> ::core::option::Option<&Self::Item> {
-                match idx {
-                    #(#arms_get,)*
-                    _ => None,
+            fn field_name(&self, idx: usize) -> ::core::option::Option<&'static str> {
+                #use_idx
+                match *self {
+                    #(#arms_field_name,)*
                 }
             }
             #[inline]
-            fn get_mut(&mut self, idx: usize) -> ::core::option::Option<&mut Self::Item> {
-                match idx {
-                    #(#arms_get_mut,)*
-                    _ => None,
+            fn field_info(&self, idx: usize) -> ::core::option::Option<&'static ::fieldmap::FieldInfo> {
+                #use_idx
+                match *self {
+                    #(#arms_field_info,)*
+                }
+            }
+            #[inline]
+            fn variant_name(&self) -> ::core::option::Option<&'static str> {
+                match *self {
+                    #(#arms_variant_name,)*
                 }
             }
+        },
+    };
+    impl_field_map(input, args, &bodies, ts);
+    Ok(())
+}
+
+fn impl_field_map(
+    input: &DeriveInput,
+    args: &ContainerArgs,
+    bodies: &FieldMapBodies,
+    ts: &mut TokenStream,
+) {
+    let item_ids = &args.item_ids;
+    let item_id = &item_ids[0];
+    let self_id = &input.ident;
+    let (impl_g, self_g, impl_where) = input.generics.split_for_impl();
+    let impl_gps = &input.generics.params;
+    let FieldMapBodies {
+        field_names,
+        find,
+        find_loose,
+        info,
+        get,
+        get_mut,
+        visit,
+        visit_fields,
+        get_many_mut,
+        split_mut_as,
+        instance,
+    } = bodies;
+
+    let get_many_mut = if get_many_mut.is_empty() {
+        TokenStream::new()
+    } else {
+        quote! {
+            fn get_many_mut<const __N: usize>(
+                &mut self,
+                idx: [usize; __N],
+            ) -> ::core::result::Result<[&mut Self::Item<'_>; __N], ::fieldmap::DisjointError> {
+                #get_many_mut
+            }
+        }
+    };
+    let code = quote_spanned! { item_id.span() =>
+        impl #impl_g ::fieldmap::Fields for #self_id #self_g #impl_where {
+            type Item<'_a> = dyn #item_id + '_a where Self: '_a;
+
+            const FIELD_NAMES: &'static [&'static str] = #field_names;
+
+            #[inline]
+            fn find(name: &str) -> ::core::option::Option<usize> {
+                #find
+            }
+            #[inline]
+            fn find_loose(name: &str) -> ::core::option::Option<usize> {
+                #find_loose
+            }
+            #[inline]
+            fn info(idx: usize) -> ::core::option::Option<&'static ::fieldmap::FieldInfo> {
+                #info
+            }
+            #[inline]
+            fn get(&self, idx: usize) -> ::core::option::Option<&Self::Item<'_>> {
+                #get
+            }
+            #[inline]
+            fn get_mut(&mut self, idx: usize) -> ::core::option::Option<&mut Self::Item<'_>> {
+                #get_mut
+            }
+            #get_many_mut
+            #instance
         }
 
         impl<'_a, #impl_gps> ::core::iter::IntoIterator for &'_a #self_id #self_g #impl_where {
-            type Item = <::fieldmap::Iter<'_a, #self_id #self_g> as Iterator>::Item;
+            type Item = <::fieldmap::Iter<'_a, #self_id #self_g> as ::core::iter::Iterator>::Item;
             type IntoIter = ::fieldmap::Iter<'_a, #self_id #self_g>;
 
             fn into_iter(self) -> Self::IntoIter {
@@ -201,7 +1466,7 @@ fn impl_field_map(
         }
 
         impl<'_a, #impl_gps> ::core::iter::IntoIterator for &'_a mut #self_id #self_g #impl_where {
-            type Item = <::fieldmap::IterMut<'_a, #self_id #self_g> as Iterator>::Item;
+            type Item = <::fieldmap::IterMut<'_a, #self_id #self_g> as ::core::iter::Iterator>::Item;
             type IntoIter = ::fieldmap::IterMut<'_a, #self_id #self_g>;
 
             fn into_iter(self) -> Self::IntoIter {
@@ -210,6 +1475,183 @@ fn impl_field_map(
         }
     };
     ts.extend(code);
+
+    let g = generics_with_item_lifetime(&input.generics);
+    let (impl_g, _, impl_where) = g.split_for_impl();
+    for item_id in item_ids {
+        let split_mut_as = if split_mut_as.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! {
+                fn split_mut_as<'_s>(
+                    &'_s mut self,
+                    idx: &[usize],
+                    offset: usize,
+                    items: &mut [::core::option::Option<&'_s mut (dyn #item_id + '_a)>],
+                ) -> ::core::result::Result<(), ::fieldmap::DisjointError> {
+                    #split_mut_as
+                }
+            }
+        };
+        let visit_fields = if visit_fields.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! {
+                fn visit_fields(
+                    &self,
+                    path: ::core::option::Option<&::fieldmap::Path>,
+                    names: &[&'static str],
+                    visitor: &mut dyn ::fieldmap::Visitor<dyn #item_id + '_a>,
+                ) {
+                    #visit_fields
+                }
+            }
+        };
+        let code = quote_spanned! { item_id.span() =>
+            impl #impl_g ::fieldmap::FieldsAs<dyn #item_id + '_a> for #self_id #self_g #impl_where {
+                #[inline]
+                fn get_as(&self, idx: usize) -> ::core::option::Option<&(dyn #item_id + '_a)> {
+                    #get
+                }
+                #[inline]
+                fn get_mut_as(&mut self, idx: usize) -> ::core::option::Option<&mut (dyn #item_id + '_a)> {
+                    #get_mut
+                }
+                #split_mut_as
+            }
+
+            impl #impl_g ::fieldmap::Visit<dyn #item_id + '_a> for #self_id #self_g #impl_where {
+                fn visit_with(
+                    &self,
+                    path: ::core::option::Option<&::fieldmap::Path>,
+                    visitor: &mut dyn ::fieldmap::Visitor<dyn #item_id + '_a>,
+                ) {
+                    #visit
+                }
+                #visit_fields
+            }
+        };
+        ts.extend(code);
+    }
+}
+
+/// Generates the struct specified by `#[fields(partial = "...")]`,
+/// which has the same fields as the input wrapped in `Option` and the same field names.
+fn impl_partial(
+    input: &DeriveInput,
+    args: &ContainerArgs,
+    partial: &Ident,
+    fields: &Fields,
+    ts: &mut TokenStream,
+) -> Result<()> {
+    let entries = field_entries(fields, args)?;
+    let mut defs = Vec::new();
+    let mut to_partial = Vec::new();
+    let mut apply_to = Vec::new();
+    let mut idx = 0;
+    for field in fields {
+        let attrs = FieldAttrs::from_attrs(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+        if let Some(span) = attrs.flatten {
+            bail!(span, "`flatten` cannot be used with `partial`.");
+        }
+        let e = &entries[idx];
+        let m = &e.member;
+        let pm = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(idx.into()),
+        };
+        let name = &e.name;
+        let aliases = &e.aliases;
+        let meta = e.meta.iter().map(|(key, value)| {
+            let key = parse_str::<Ident>(key).unwrap_or_else(|_| Ident::new_raw(key, e.span));
+            quote!(#[fields(meta(#key = #value))])
+        });
+        let docs = field.attrs.iter().filter(|a| a.path.is_ident("doc"));
+        let vis = &field.vis;
+        let colon = field.ident.as_ref().map(|ident| quote!(#ident:));
+        let ty = &field.ty;
+        defs.push(quote! {
+            #(#docs)*
+            #[fields(rename = #name)]
+            #(#[fields(alias = #aliases)])*
+            #(#meta)*
+            #vis #colon ::core::option::Option<#ty>
+        });
+        to_partial.push(quote_spanned!(field.span()=>
+            #pm: ::core::option::Option::Some(::core::clone::Clone::clone(&self.#m))
+        ));
+        apply_to.push(quote_spanned! { field.span()=>
+            if let ::core::option::Option::Some(value) = &self.#pm {
+                ::core::clone::Clone::clone_from(&mut target.#m, value);
+            }
+        });
+        idx += 1;
+    }
+    let item_ids = &args.item_ids;
+    let vis = &input.vis;
+    let self_id = &input.ident;
+    let generics = &input.generics;
+    let (impl_g, self_g, impl_where) = generics.split_for_impl();
+    let where_clause = &generics.where_clause;
+    let body = match fields {
+        Fields::Named(_) => quote!(#where_clause { #(#defs,)* }),
+        Fields::Unnamed(_) => quote!(( #(#defs,)* ) #where_clause;),
+        Fields::Unit => quote!(#where_clause;),
+    };
+    let doc_partial = format!(
+        "The fields of [`{}`] wrapped in `Option`, generated by `#[fields(partial = \"{}\")]`.",
+        self_id, partial
+    );
+    let doc_to_partial = format!("Returns a [`{}`] with all fields set.", partial);
+    let doc_apply_to = format!(
+        "Assigns the fields that are `Some` to the fields of [`{}`] with the same name.",
+        self_id
+    );
+    ts.extend(quote! {
+        #[doc = #doc_partial]
+        #[derive(::fieldmap::Fields, ::core::clone::Clone, ::core::default::Default)]
+        #(#[fields(item = #item_ids)])*
+        #vis struct #partial #generics #body
+
+        impl #impl_g #self_id #self_g #impl_where {
+            #[doc = #doc_to_partial]
+            #vis fn to_partial(&self) -> #partial #self_g {
+                #partial { #(#to_partial,)* }
+            }
+        }
+        impl #impl_g #partial #self_g #impl_where {
+            #[doc = #doc_apply_to]
+            #vis fn apply_to(&self, target: &mut #self_id #self_g) {
+                #(#apply_to)*
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Adds `'_a` that every lifetime and type parameter outlives.
+fn generics_with_item_lifetime(generics: &Generics) -> Generics {
+    let mut g = generics.clone();
+    let lt: Lifetime = parse_quote!('_a);
+    let wc = g.make_where_clause();
+    for p in &generics.params {
+        match p {
+            GenericParam::Lifetime(p) => {
+                let l = &p.lifetime;
+                wc.predicates.push(parse_quote!(#l: #lt));
+            }
+            GenericParam::Type(p) => {
+                let t = &p.ident;
+                wc.predicates.push(parse_quote!(#t: #lt));
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+    g.params.insert(0, parse_quote!(#lt));
+    g
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -226,6 +1668,15 @@ impl FieldKey {
             FieldKey::Unnamed(idx)
         }
     }
+    fn to_name(&self, attrs: &FieldAttrs, rename_all: Option<RenameRule>) -> String {
+        if let Some(rename) = &attrs.rename {
+            return rename.value();
+        }
+        match (self, rename_all) {
+            (FieldKey::Named(_), Some(rule)) => rule.apply(&self.to_string()),
+            _ => self.to_string(),
+        }
+    }
     fn to_member(&self) -> Member {
         match self {
             FieldKey::Named(ident) => Member::Named(ident.clone()),
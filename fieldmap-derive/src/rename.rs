@@ -0,0 +1,62 @@
+use syn::{LitStr, Result};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub fn from_lit(lit: &LitStr) -> Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => bail!(
+                lit.span(),
+                "unknown rename rule `{}`. expected one of `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`, `SCREAMING-KEBAB-CASE`.",
+                lit.value()
+            ),
+        })
+    }
+
+    /// Applies the rule to a snake_case field name.
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => name.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => name.to_ascii_uppercase(),
+            RenameRule::Pascal => to_camel(name, true),
+            RenameRule::Camel => to_camel(name, false),
+            RenameRule::Kebab => name.replace('_', "-"),
+            RenameRule::ScreamingKebab => name.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+fn to_camel(name: &str, upper_first: bool) -> String {
+    let body = name.trim_start_matches('_');
+    let mut s = name[..name.len() - body.len()].to_owned();
+    let mut upper = upper_first;
+    for c in body.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            s.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            s.push(c);
+        }
+    }
+    s
+}
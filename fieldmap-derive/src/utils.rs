@@ -1,5 +1,6 @@
-use proc_macro2::TokenStream;
-use syn::Result;
+use proc_macro2::{Span, TokenStream};
+use std::fmt::Display;
+use syn::{Error, Result};
 
 macro_rules! bail {
     ($span:expr, $message:literal $(,)?) => {
@@ -20,3 +21,15 @@ pub fn into_macro_output(input: Result<TokenStream>) -> proc_macro::TokenStream
     }
     .into()
 }
+
+/// Creates an error at `span` with a note pointing at `note_span`.
+pub fn error_with_note(
+    span: Span,
+    message: impl Display,
+    note_span: Span,
+    note: impl Display,
+) -> Error {
+    let mut e = Error::new(span, message);
+    e.combine(Error::new(note_span, note));
+    e
+}
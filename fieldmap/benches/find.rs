@@ -0,0 +1,260 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+macro_rules! wide_type {
+    ($($name:ident)*) => {
+        #[derive(Fields)]
+        #[fields(item = Debug)]
+        #[allow(dead_code)]
+        struct Wide {
+            $($name: u32,)*
+        }
+
+        #[allow(non_camel_case_types)]
+        enum Idx {
+            $($name,)*
+        }
+
+        /// Lookup by a `match` on `&str`, as `#[derive(Fields)]` generated before.
+        fn find_match(name: &str) -> Option<usize> {
+            match name {
+                $(stringify!($name) => Some(Idx::$name as usize),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+wide_type! {
+    cpu_load
+    cpu_temp
+    cpu_rate
+    cpu_errors
+    cpu_count
+    cpu_peak
+    cpu_avg
+    cpu_min
+    cpu_max
+    cpu_total
+    cpu_drops
+    cpu_retries
+    cpu_latency
+    cpu_usage
+    cpu_level
+    cpu_speed
+    cpu_volt
+    cpu_power
+    cpu_alarm
+    cpu_state
+    cpu_queue
+    cpu_wait
+    cpu_busy
+    cpu_idle
+    cpu_flags
+    mem_load
+    mem_temp
+    mem_rate
+    mem_errors
+    mem_count
+    mem_peak
+    mem_avg
+    mem_min
+    mem_max
+    mem_total
+    mem_drops
+    mem_retries
+    mem_latency
+    mem_usage
+    mem_level
+    mem_speed
+    mem_volt
+    mem_power
+    mem_alarm
+    mem_state
+    mem_queue
+    mem_wait
+    mem_busy
+    mem_idle
+    mem_flags
+    disk_load
+    disk_temp
+    disk_rate
+    disk_errors
+    disk_count
+    disk_peak
+    disk_avg
+    disk_min
+    disk_max
+    disk_total
+    disk_drops
+    disk_retries
+    disk_latency
+    disk_usage
+    disk_level
+    disk_speed
+    disk_volt
+    disk_power
+    disk_alarm
+    disk_state
+    disk_queue
+    disk_wait
+    disk_busy
+    disk_idle
+    disk_flags
+    net_load
+    net_temp
+    net_rate
+    net_errors
+    net_count
+    net_peak
+    net_avg
+    net_min
+    net_max
+    net_total
+    net_drops
+    net_retries
+    net_latency
+    net_usage
+    net_level
+    net_speed
+    net_volt
+    net_power
+    net_alarm
+    net_state
+    net_queue
+    net_wait
+    net_busy
+    net_idle
+    net_flags
+    gpu_load
+    gpu_temp
+    gpu_rate
+    gpu_errors
+    gpu_count
+    gpu_peak
+    gpu_avg
+    gpu_min
+    gpu_max
+    gpu_total
+    gpu_drops
+    gpu_retries
+    gpu_latency
+    gpu_usage
+    gpu_level
+    gpu_speed
+    gpu_volt
+    gpu_power
+    gpu_alarm
+    gpu_state
+    gpu_queue
+    gpu_wait
+    gpu_busy
+    gpu_idle
+    gpu_flags
+    fan_load
+    fan_temp
+    fan_rate
+    fan_errors
+    fan_count
+    fan_peak
+    fan_avg
+    fan_min
+    fan_max
+    fan_total
+    fan_drops
+    fan_retries
+    fan_latency
+    fan_usage
+    fan_level
+    fan_speed
+    fan_volt
+    fan_power
+    fan_alarm
+    fan_state
+    fan_queue
+    fan_wait
+    fan_busy
+    fan_idle
+    fan_flags
+    psu_load
+    psu_temp
+    psu_rate
+    psu_errors
+    psu_count
+    psu_peak
+    psu_avg
+    psu_min
+    psu_max
+    psu_total
+    psu_drops
+    psu_retries
+    psu_latency
+    psu_usage
+    psu_level
+    psu_speed
+    psu_volt
+    psu_power
+    psu_alarm
+    psu_state
+    psu_queue
+    psu_wait
+    psu_busy
+    psu_idle
+    psu_flags
+    bus_load
+    bus_temp
+    bus_rate
+    bus_errors
+    bus_count
+    bus_peak
+    bus_avg
+    bus_min
+    bus_max
+    bus_total
+    bus_drops
+    bus_retries
+    bus_latency
+    bus_usage
+    bus_level
+    bus_speed
+    bus_volt
+    bus_power
+    bus_alarm
+    bus_state
+    bus_queue
+    bus_wait
+    bus_busy
+    bus_idle
+    bus_flags
+}
+
+fn bench_find(c: &mut Criterion) {
+    let names: Vec<String> = Wide::names()
+        .map(|name| name.to_string())
+        .chain(["unknown".to_string(), "cpu_unknown".to_string()])
+        .collect();
+    assert!(names
+        .iter()
+        .all(|name| Wide::find(name) == find_match(name)));
+
+    let mut group = c.benchmark_group("find");
+    group.bench_function("derive", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(Wide::find(black_box(name)));
+            }
+        })
+    });
+    group.bench_function("match", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(find_match(black_box(name)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_find);
+criterion_main!(benches);
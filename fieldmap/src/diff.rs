@@ -0,0 +1,136 @@
+use crate::Fields;
+use core::any::Any;
+use core::iter::FusedIterator;
+
+/// A value that can be compared with a value of unknown type.
+///
+/// This trait is object safe and implemented for all types that implement [`PartialEq`] and [`Any`].
+/// Use `#[fields(item = DynEq)]` to make a type usable with [`diff`].
+pub trait DynEq {
+    /// Returns `self` as `dyn Any` for downcasting.
+    fn as_any(&self) -> &dyn Any;
+    /// Returns `true` if `other` is of the same type as `self` and equal to `self`.
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: PartialEq + Any> DynEq for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn dyn_eq(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// Returns an iterator over the fields that differ between `a` and `b`.
+///
+/// Fields are paired by index.
+/// For enums whose active variants differ, every field of `a` is returned with `new` set to `None`,
+/// followed by every field of `b` with `old` set to `None`.
+///
+/// ```rust
+/// use fieldmap::{DynEq, Fields};
+///
+/// #[derive(Fields)]
+/// #[fields(item = DynEq)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let a = Config { host: "localhost".into(), port: 80 };
+/// let b = Config { host: "localhost".into(), port: 8080 };
+/// let changes: Vec<_> = fieldmap::diff(&a, &b).map(|c| c.name).collect();
+/// assert_eq!(changes, ["port"]);
+/// ```
+pub fn diff<'a, S>(a: &'a S, b: &'a S) -> Diff<'a, S>
+where
+    S: Fields,
+    S::Item<'a>: DynEq,
+{
+    let same_variant = a.variant_name() == b.variant_name();
+    Diff {
+        a,
+        b,
+        idx: 0,
+        same_variant,
+    }
+}
+
+/// A change of a field, returned by [`diff`].
+pub struct FieldChange<'a, S: Fields + 'a> {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The index of the field in the value that has it.
+    pub index: usize,
+    /// The value of the field in the first value, or `None` if the active variant of the first value does not have it.
+    pub old: Option<&'a S::Item<'a>>,
+    /// The value of the field in the second value, or `None` if the active variant of the second value does not have it.
+    pub new: Option<&'a S::Item<'a>>,
+}
+
+impl<'a, S: Fields> Clone for FieldChange<'a, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, S: Fields> Copy for FieldChange<'a, S> {}
+
+/// An iterator over the changed fields, returned by [`diff`].
+pub struct Diff<'a, S> {
+    a: &'a S,
+    b: &'a S,
+    idx: usize,
+    same_variant: bool,
+}
+
+impl<'a, S> Iterator for Diff<'a, S>
+where
+    S: Fields,
+    S::Item<'a>: DynEq,
+{
+    type Item = FieldChange<'a, S>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.same_variant {
+            let a_len = self.a.field_len();
+            let (s, index) = if self.idx < a_len {
+                (self.a, self.idx)
+            } else {
+                (self.b, self.idx - a_len)
+            };
+            let value = s.get(index)?;
+            self.idx += 1;
+            let (old, new) = if self.idx <= a_len {
+                (Some(value), None)
+            } else {
+                (None, Some(value))
+            };
+            return Some(FieldChange {
+                name: s.field_name(index).unwrap_or_default(),
+                index,
+                old,
+                new,
+            });
+        }
+        while let (Some(old), Some(new)) = (self.a.get(self.idx), self.b.get(self.idx)) {
+            let index = self.idx;
+            self.idx += 1;
+            if !old.dyn_eq(new.as_any()) {
+                let name = self.a.field_name(index).unwrap_or_default();
+                return Some(FieldChange {
+                    name,
+                    index,
+                    old: Some(old),
+                    new: Some(new),
+                });
+            }
+        }
+        None
+    }
+}
+impl<'a, S> FusedIterator for Diff<'a, S>
+where
+    S: Fields,
+    S::Item<'a>: DynEq,
+{
+}
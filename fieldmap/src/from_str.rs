@@ -0,0 +1,116 @@
+use core::any::type_name;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+
+/// A value that can be assigned from a string.
+///
+/// This trait is object safe and implemented for all types that implement [`FromStr`].
+/// Use `#[fields(item = FromStrItem)]` to make a type usable with [`Fields::set_str`](crate::Fields::set_str).
+pub trait FromStrItem {
+    /// Parses `s` and assigns the result to `self`.
+    ///
+    /// `self` is left unchanged if `s` cannot be parsed.
+    fn set_from_str(&mut self, s: &str) -> Result<(), ParseError>;
+}
+
+impl<T> FromStrItem for T
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn set_from_str(&mut self, s: &str) -> Result<(), ParseError> {
+        match s.parse() {
+            Ok(value) => {
+                *self = value;
+                Ok(())
+            }
+            Err(e) => Err(ParseError::new::<T>(&e)),
+        }
+    }
+}
+
+/// An error of [`FromStrItem::set_from_str`] that the string could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    ty: &'static str,
+    #[cfg(feature = "alloc")]
+    message: String,
+}
+
+impl ParseError {
+    fn new<T>(e: &dyn fmt::Display) -> Self {
+        #[cfg(not(feature = "alloc"))]
+        let _ = e;
+        Self {
+            ty: type_name::<T>(),
+            #[cfg(feature = "alloc")]
+            message: e.to_string(),
+        }
+    }
+
+    /// Returns the name of the type that the string was parsed as.
+    pub fn ty(&self) -> &'static str {
+        self.ty
+    }
+    /// Returns the message of the error returned by [`FromStr::from_str`].
+    #[cfg(feature = "alloc")]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid value for type `{}`", self.ty)?;
+        #[cfg(feature = "alloc")]
+        write!(f, ": {}", self.message)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// An error of [`Fields::set_str`](crate::Fields::set_str).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetStrError<'a> {
+    /// There is no field named `name`.
+    NotFound { name: &'a str },
+    /// The value could not be parsed as the type of the field named `name`.
+    Parse {
+        name: &'static str,
+        error: ParseError,
+    },
+}
+
+impl<'a> SetStrError<'a> {
+    /// Returns the name of the field.
+    pub fn name(&self) -> &'a str {
+        match self {
+            Self::NotFound { name } => name,
+            Self::Parse { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for SetStrError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound { name } => write!(f, "field `{}` was not found", name),
+            Self::Parse { name, error } => write!(f, "field `{}`: {}", name, error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetStrError<'_> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotFound { .. } => None,
+            Self::Parse { error, .. } => Some(error),
+        }
+    }
+}
@@ -28,7 +28,45 @@ assert_eq!(*Field::<u16>::get(&x), 200);
 assert_eq!(*Field::<String>::get(&x), "300");
 ```
 
-`#[derive(Field)]` can use only struct with different type of each field.
+`#[derive(Field)]` implements `Field<T>` only for types used by exactly one field.
+If two field types are different but may be the same type, such as `T` and `u32`, their implementations would conflict, so `#[derive(Field)]` reports an error.
+Several fields of exactly the same type are not an error, because they can still be accessed with [`FieldByTag`].
+Instead, using `Field<T>` for such a type reports an error at the use site.
+
+`#[derive(Field)]` also implements [`FieldByTag`], which access a field by a tag type instead of the field type.
+So it works even when several fields have the same type.
+
+```rust
+use fieldmap::FieldByTag;
+
+#[derive(fieldmap::Field)]
+struct ExampleType {
+    x: u32,
+    y: u32,
+}
+
+let x = ExampleType { x: 1, y: 2 };
+assert_eq!(*FieldByTag::<ExampleType_fields::x>::get(&x), 1);
+assert_eq!(*FieldByTag::<ExampleType_fields::y>::get(&x), 2);
+```
+
+For enums, `#[derive(Field)]` implements [`TryField`] for each field type instead.
+`TryField::try_get` returns `Some` when the active variant has a field of that type.
+As with structs, `TryField` is not implemented for types used by several fields of one variant.
+
+```rust
+use fieldmap::TryField;
+
+#[derive(fieldmap::Field)]
+enum Shape {
+    Circle { r: f64 },
+    Rect(f64, u32),
+}
+
+let x = Shape::Rect(1.0, 2);
+assert_eq!(TryField::<f64>::try_get(&x), Some(&1.0));
+assert_eq!(TryField::<u32>::try_get(&Shape::Circle { r: 1.0 }), None);
+```
 
 ## Derive `Fields`
 
@@ -65,41 +103,682 @@ value_u16 = 200
 value_s = "300"
 ```
 
-## Limitation
-Only `'static` type can implement [`Fields`].
-Because this limitation is caused by Rust not supporting GAT (generic associated types),
-so the limitation may be removed in the future.
+### Field attributes
+
+| attribute                      | effect                                                      |
+| ------------------------------ | ----------------------------------------------------------- |
+| `#[fields(skip)]`              | Excludes the field. Indices of the other fields stay dense. |
+| `#[fields(rename = "name")]`   | Uses `name` as the field name.                              |
+| `#[fields(alias = "name")]`    | Makes `Fields::find` also accept `name`. Can be repeated.   |
+| `#[fields(meta(key = "value"))]` | Adds a key-value pair to `FieldInfo::meta`. Can be repeated. |
+| `#[fields(flatten)]`           | Splices the fields of the field's type into this type.      |
+| `#[fields(prefix = "...")]`   | Prepends a prefix to the names of flattened fields.         |
+| `#[fields(nested)]`            | Makes `Visit` traverse the field instead of passing it to `Visitor::leaf`. |
+
+### Container attributes
+
+| attribute                      | effect                                                      |
+| ------------------------------ | ----------------------------------------------------------- |
+| `#[fields(item = Trait)]`      | Views the fields as `dyn Trait`. Can be repeated.           |
+| `#[fields(rename_all = "...")]`| Converts snake_case field names to `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"` or `"SCREAMING-KEBAB-CASE"`. |
+| `#[fields(partial = "Name")]`  | Generates a struct `Name` with the fields wrapped in `Option`. |
+
+## Borrowed fields
+
+`Fields::Item` is a generic associated type, so structs that borrow can implement [`Fields`].
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = "Debug")]
+struct Row<'a> {
+    name: &'a str,
+    value: u8,
+}
+
+let s = String::from("abc");
+let x = Row { name: &s, value: 10 };
+assert_eq!(format!("{:?}", x.get(0).unwrap()), "\"abc\"");
+```
+
+## Enums
+
+For enums, [`Fields::get`] and the instance-level methods such as [`Fields::field_len`] and
+[`Fields::field_name`] describe the fields of the active variant.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+enum Shape {
+    Circle { r: f64 },
+    Rect { w: f64, h: f64 },
+}
+
+let x = Shape::Rect { w: 1.0, h: 2.0 };
+assert_eq!(x.variant_name(), Some("Rect"));
+assert_eq!(x.field_len(), 2);
+assert_eq!(x.field_name(1), Some("h"));
+```
+
+## Multiple item traits
+
+Repeat `#[fields(item = ...)]` to view the fields as several trait objects.
+The first one becomes [`Fields::Item`], and every one of them implements [`FieldsAs`].
+
+```rust
+use fieldmap::{Fields, FieldsAs};
+use std::any::Any;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(item = Any)]
+struct ExampleType {
+    value_u8: u8,
+    value_s: String,
+}
+
+let x = ExampleType {
+    value_u8: 100,
+    value_s: "300".into(),
+};
+
+for (name, value) in FieldsAs::<dyn Debug>::iter_as(&x) {
+    println!("{} = {:?}", name, value);
+}
+let value: &dyn Any = x.get_as(0).unwrap();
+assert_eq!(value.downcast_ref::<u8>(), Some(&100));
+```
+
+## Loose lookup
+
+[`Fields::find_loose`] finds a field ignoring ASCII case, `_` and `-`,
+which is useful for names from INI files or environment variables.
+`#[derive(Fields)]` reports an error if two field names of a type match each other in this way.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType {
+    value_u8: u8,
+}
+
+assert_eq!(ExampleType::find_loose("VALUE_U8"), Some(0));
+assert_eq!(ExampleType::find_loose("value-u8"), Some(0));
+assert_eq!(ExampleType::find_loose("valueU8"), Some(0));
+```
+
+## Set fields from strings
+
+With `#[fields(item = FromStrItem)]`, [`Fields::set_str`] parses a string and assigns it to the field with the given name,
+which is useful for command line overrides such as `--set key=value`.
+Errors carry the name of the field.
+
+```rust
+use fieldmap::{Fields, FromStrItem};
+
+#[derive(Fields)]
+#[fields(item = FromStrItem)]
+struct ExampleType {
+    value_u8: u8,
+    value_s: String,
+}
+
+let mut x = ExampleType {
+    value_u8: 100,
+    value_s: "300".into(),
+};
+x.set_str("value_u8", "10").unwrap();
+assert_eq!(x.value_u8, 10);
+
+let e = x.set_str("value_u8", "abc").unwrap_err();
+assert_eq!(e.name(), "value_u8");
+```
+
+## Diff
+
+With `#[fields(item = DynEq)]`, [`diff`] returns the fields that differ between two values,
+with the name, index, old value and new value of each field.
+For enums whose active variants differ, every field of both values is returned, with `None` for the value that does not have it.
+
+```rust
+use fieldmap::{DynEq, Fields};
+
+#[derive(Fields)]
+#[fields(item = DynEq)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+let a = Config { host: "localhost".into(), port: 80 };
+let b = Config { host: "localhost".into(), port: 8080 };
+for c in fieldmap::diff(&a, &b) {
+    assert_eq!(c.name, "port");
+    assert_eq!(c.index, 1);
+    assert_eq!(c.new.unwrap().as_any().downcast_ref::<u16>(), Some(&8080));
+}
+```
+
+## Patch and merge
+
+With `#[fields(item = MergeItem)]`, [`Fields::merge_from`] copies the fields of another value
+for which a predicate returns `true`, and `Patch` holds a partial update keyed by field index (requires the `alloc` feature).
+This is useful for layered configuration such as defaults < file < environment < command line.
+With the `serde` feature, a `Patch` serializes and deserializes as a map of the fields that are set.
+
+```rust
+# #[cfg(feature = "alloc")] {
+use fieldmap::{Fields, MergeItem, Patch};
+
+#[derive(Fields, Default)]
+#[fields(item = MergeItem)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+let mut config = Config { host: "localhost".into(), port: 80 };
+
+let mut cli = Patch::<Config>::default();
+cli.set(Config::find("port").unwrap(), &8080u16);
+cli.apply(&mut config);
+assert_eq!(config.port, 8080);
+
+let env = Config { host: "example.com".into(), port: 0 };
+config.merge_from(&env, |_, value| value.downcast_ref::<u16>() != Some(&0));
+assert_eq!(config.host, "example.com");
+assert_eq!(config.port, 8080);
+# }
+```
+
+## Partial structs
+
+`#[fields(partial = "Name")]` generates a struct `Name` with the same fields wrapped in `Option`,
+which also implements [`Fields`] with the same `item`s and the same field names and indices.
+`Name::apply_to` assigns the fields that are `Some` to a value, and `to_partial` converts a value to `Name` with all fields set.
+The fields must implement `Clone`, `Option` of them must implement the `item`s, and `#[fields(flatten)]` cannot be used.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug, partial = "PartialConfig")]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+let mut config = Config { host: "localhost".into(), port: 80 };
+let cli = PartialConfig { host: None, port: Some(8080) };
+cli.apply_to(&mut config);
+assert_eq!(config.port, 8080);
+assert_eq!(PartialConfig::FIELD_NAMES, Config::FIELD_NAMES);
+assert_eq!(config.to_partial().host.as_deref(), Some("localhost"));
+```
+
+## Change tracking
+
+[`Tracked`] wraps a value and marks the fields returned by [`Tracked::get_mut`] and [`Tracked::iter_mut`] as dirty,
+which is useful for sending only the changed fields to a remote peer.
+The dirty bits are stored in a fixed-size array, and `Tracked::set_on_change` registers a function called for each field marked as dirty.
+[`Tracked::new`] supports types with up to 64 fields, and [`Tracked::new_wide`] supports more.
+
+```rust
+use fieldmap::{Fields, Tracked};
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+let mut x = Tracked::new(Config { host: "localhost".into(), port: 80 });
+x.get_mut(1);
+assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["port"]);
+x.clear_dirty();
+assert_eq!(x.dirty().count(), 0);
+```
+
+## Borrow several fields mutably
+
+[`Fields::get_many_mut`] returns mutable references to several fields at the same time,
+and [`Fields::get_many_mut_by_name`] does the same by field name.
+They return [`DisjointError`] if a field is not found or requested more than once.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType {
+    value_u8: u8,
+    value_u16: u16,
+    value_s: String,
+}
+
+let mut x = ExampleType {
+    value_u8: 100,
+    value_u16: 200,
+    value_s: "300".into(),
+};
+let [s, a] = x.get_many_mut_by_name(["value_s", "value_u8"]).unwrap();
+println!("{:?} {:?}", s, a);
+assert!(x.get_many_mut([0, 0]).is_err());
+```
+
+## Flatten
+
+`#[fields(flatten)]` splices the fields of a field that also implements [`Fields`] into the parent.
+`#[fields(prefix = "...")]` prepends a prefix to the names of the spliced fields.
+The type of a flattened field must implement [`FieldsAs`] and [`Visit`] for every `item` of the parent and must not use generic parameters.
+[`Fields::info`] of a spliced field returns the metadata of the inner type, so its `name` does not include the prefix.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct DbConfig {
+    host: String,
+    port: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct App {
+    name: String,
+    #[fields(flatten, prefix = "db.")]
+    db: DbConfig,
+}
+
+assert_eq!(App::FIELD_NAMES, ["name", "db.host", "db.port"]);
+assert_eq!(App::find("db.port"), Some(2));
+```
+
+## Path access
+
+With `#[fields(item = PathItem)]`, [`FieldsPath`] accesses nested fields by dotted path.
+Numeric segments select fields of tuple structs and elements of arrays and `Vec`.
+
+```rust
+# #[cfg(feature = "alloc")] {
+use fieldmap::{Fields, FieldsPath, PathItem};
+
+#[derive(Fields)]
+#[fields(item = PathItem)]
+struct Db {
+    hosts: Vec<String>,
+}
+
+#[derive(Fields)]
+#[fields(item = PathItem)]
+struct App {
+    db: Db,
+}
+
+let x = App {
+    db: Db {
+        hosts: vec!["a".into(), "b".into()],
+    },
+};
+let host = x.get_path("db.hosts.1").unwrap();
+assert_eq!(host.downcast_ref::<String>().unwrap(), "b");
+
+let e = x.get_path("db.ports").err().unwrap();
+assert_eq!(e.segment(), "ports");
+
+x.walk(&mut |path, value| {
+    if let Some(value) = value.downcast_ref::<String>() {
+        println!("{} = {}", path, value);
+    }
+});
+# }
+```
+
+## Visitor
+
+[`Visit::visit`] traverses nested values with a [`Visitor`].
+Fields with `#[fields(nested)]` are traversed recursively, and the other fields are passed to [`Visitor::leaf`] with their full path.
+Fields spliced by `#[fields(flatten)]` are visited with their prefixed names, and their own `#[fields(nested)]` fields are traversed as well.
+
+```rust
+use fieldmap::{Fields, Path, Visit, Visitor};
+use std::fmt::Debug;
+
+#[derive(Fields, Debug)]
+#[fields(item = Debug)]
+struct DbConfig {
+    host: String,
+    port: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct App {
+    name: String,
+    #[fields(nested)]
+    db: DbConfig,
+}
+
+struct Dump(Vec<String>);
+
+impl Visitor<dyn Debug> for Dump {
+    fn leaf(&mut self, path: &Path, value: &dyn Debug) {
+        self.0.push(format!("{} = {:?}", path, value));
+    }
+}
+
+let x = App {
+    name: "app".into(),
+    db: DbConfig {
+        host: "localhost".into(),
+        port: 5432,
+    },
+};
+let mut d = Dump(Vec::new());
+x.visit(&mut d);
+assert_eq!(d.0, ["name = \"app\"", "db.host = \"localhost\"", "db.port = 5432"]);
+```
+
+## Serde
+
+With the `serde` feature, `serde::AsMap` serializes a value as a map of field name to value,
+and `serde::deserialize_fields` fills the fields of a value from such a map.
+Unknown keys and missing fields are reported as errors.
+
+```rust
+# #[cfg(feature = "serde")] {
+use fieldmap::serde::{deserialize_fields, erased_serde, AsMap, DeserializeItem};
+use fieldmap::Fields;
+
+#[derive(Fields)]
+#[fields(item = erased_serde::Serialize)]
+#[fields(item = DeserializeItem)]
+struct ExampleType {
+    value_u8: u8,
+    value_s: String,
+}
+
+let mut x = ExampleType {
+    value_u8: 100,
+    value_s: "300".into(),
+};
+let s = serde_json::to_string(&AsMap(&x)).unwrap();
+assert_eq!(s, r#"{"value_u8":100,"value_s":"300"}"#);
+
+let json = r#"{"value_u8":1,"value_s":"a"}"#;
+deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap();
+assert_eq!(x.value_u8, 1);
+# }
+```
+
+## Constants
+
+[`Fields::FIELD_NAMES`] and [`Fields::LEN`] are associated constants, so they can be used in const contexts.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType {
+    value_u8: u8,
+    value_u16: u16,
+}
+
+const NAMES: &[&str] = ExampleType::FIELD_NAMES;
+let counts = [0u32; <ExampleType as Fields>::LEN];
+assert_eq!(NAMES, ["value_u8", "value_u16"]);
+assert_eq!(counts.len(), 2);
+```
+
+## Field metadata
+
+[`Fields::info`] returns a [`FieldInfo`] with the type, doc comment and visibility of a field.
+Use `#[fields(meta(key = "value", ...))]` to attach arbitrary key-value pairs.
+
+```rust
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType {
+    /// Port number.
+    #[fields(meta(label = "Port"))]
+    pub port: u16,
+}
+
+let info = ExampleType::info(0).unwrap();
+assert_eq!(info.ty, "u16");
+assert_eq!(info.doc, "Port number.");
+assert_eq!(info.vis, "pub");
+assert_eq!(info.meta("label"), Some("Port"));
+```
+
+## `no_std`
+
+`fieldmap` is `#![no_std]`, and the code generated by the derive macros only uses `::core`.
+To use it without `std`, disable the default features.
+
+```toml
+[dependencies]
+fieldmap = { version = "0.1.0", default-features = false }
+```
+
+The `std` feature (enabled by default) and the `alloc` feature are reserved for APIs that need allocation.
+The `serde` feature requires `alloc`.
 */
+#![no_std]
 
-use std::iter::FusedIterator;
-use std::marker::PhantomData;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+use core::any::Any;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+pub use diff::{diff, Diff, DynEq, FieldChange};
 pub use fieldmap_derive::{Field, Fields};
+pub use from_str::{FromStrItem, ParseError, SetStrError};
+pub use patch::MergeItem;
+#[cfg(feature = "alloc")]
+pub use patch::Patch;
+pub use path::{FieldsPath, Path, PathError, PathItem, Segment};
+pub use tracked::{Tracked, TrackedIterMut};
+pub use visit::{Visit, Visitor};
+
+mod diff;
+mod from_str;
+mod patch;
+mod path;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod tracked;
+mod visit;
 
 /// An interface for access all fields.
 ///
 /// See the [module-level documentation](index.html) for more details.
-pub trait Fields: Sized + 'static {
-    // TODO : use generic associated type. (`type Item<'a> : ?Sized;`)
-    type Item: ?Sized;
+pub trait Fields: Sized {
+    type Item<'a>: ?Sized
+    where
+        Self: 'a;
+
+    /// The names of the fields, in index order.
+    ///
+    /// For enums, the fields depend on the active variant, so this is empty.
+    const FIELD_NAMES: &'static [&'static str];
 
-    fn len() -> usize;
+    /// The number of fields. Usable in const contexts such as array lengths.
+    const LEN: usize = Self::FIELD_NAMES.len();
+
+    /// Returns the number of fields.
+    ///
+    /// For enums, the fields depend on the active variant, so this returns 0.
+    /// Use [`Fields::field_len`] instead.
+    fn len() -> usize {
+        Self::LEN
+    }
+    /// Returns the index of the field named `name`.
+    ///
+    /// `#[derive(Fields)]` implements this as a match on the length and bytes of `name`,
+    /// which usually compares fewer bytes than testing each field name in turn.
     fn find(name: &str) -> Option<usize>;
-    fn name(idx: usize) -> Option<&'static str>;
+    /// Returns the index of the field whose name matches `name` ignoring ASCII case, `_` and `-`.
+    ///
+    /// For example, `VALUE_U8`, `value-u8` and `valueU8` all match the field `value_u8`.
+    /// `#[derive(Fields)]` rejects types with field names that match each other in this way.
+    fn find_loose(name: &str) -> Option<usize> {
+        Self::FIELD_NAMES
+            .iter()
+            .position(|n| loose_bytes(n).eq(loose_bytes(name)))
+    }
+    fn name(idx: usize) -> Option<&'static str> {
+        Self::FIELD_NAMES.get(idx).copied()
+    }
+    /// Returns the metadata of the field at `idx`.
+    ///
+    /// The default implementation returns `None`.
+    fn info(idx: usize) -> Option<&'static FieldInfo> {
+        let _ = idx;
+        None
+    }
 
-    fn get(&self, idx: usize) -> Option<&Self::Item>;
-    fn get_mut(&mut self, idx: usize) -> Option<&mut Self::Item>;
+    fn get(&self, idx: usize) -> Option<&Self::Item<'_>>;
+    /// Returns the field at `idx`.
+    ///
+    /// Implementations must return different fields for different indices.
+    /// [`Fields::iter_mut`] and [`Fields::values_mut`] rely on this to return mutable references to several fields at once.
+    fn get_mut(&mut self, idx: usize) -> Option<&mut Self::Item<'_>>;
 
-    fn iter(&self) -> Iter<Self> {
+    /// Returns the number of fields of this value.
+    ///
+    /// Same as [`Fields::len`] except for enums, where it is the number of fields of the active variant.
+    fn field_len(&self) -> usize {
+        Self::len()
+    }
+    /// Returns the index of the field of this value named `name`.
+    fn field_find(&self, name: &str) -> Option<usize> {
+        Self::find(name)
+    }
+    /// Returns the index of the field of this value whose name matches `name` as [`Fields::find_loose`] does.
+    fn field_find_loose(&self, name: &str) -> Option<usize> {
+        Self::find_loose(name)
+    }
+    /// Returns the name of the field of this value at `idx`.
+    fn field_name(&self, idx: usize) -> Option<&'static str> {
+        Self::name(idx)
+    }
+    /// Returns the metadata of the field of this value at `idx`.
+    fn field_info(&self, idx: usize) -> Option<&'static FieldInfo> {
+        Self::info(idx)
+    }
+    /// Returns the name of the active variant, or `None` if `Self` is not an enum.
+    fn variant_name(&self) -> Option<&'static str> {
+        None
+    }
+    /// Parses `value` and assigns it to the field of this value named `name`.
+    ///
+    /// Requires `#[fields(item = FromStrItem)]`.
+    fn set_str<'a, 'n>(&mut self, name: &'n str, value: &str) -> Result<(), SetStrError<'n>>
+    where
+        Self: FieldsAs<dyn FromStrItem + 'a>,
+    {
+        let Some(idx) = self.field_find(name) else {
+            return Err(SetStrError::NotFound { name });
+        };
+        let name = self.field_name(idx).unwrap_or_default();
+        let item: &mut (dyn FromStrItem + 'a) = self.get_mut_as(idx).unwrap();
+        item.set_from_str(value)
+            .map_err(|error| SetStrError::Parse { name, error })
+    }
+    /// Copies the fields of `other` for which `f` returns `true` to this value.
+    ///
+    /// `f` is called with the index and the value of each field of `other`.
+    /// Requires `#[fields(item = MergeItem)]`.
+    fn merge_from<'a>(&mut self, other: &Self, mut f: impl FnMut(usize, &dyn Any) -> bool)
+    where
+        Self: FieldsAs<dyn MergeItem + 'a>,
+    {
+        for idx in 0..other.field_len() {
+            if let (Some(src), Some(dst)) = (other.get_as(idx), self.get_mut_as(idx)) {
+                if f(idx, src.merge_any()) {
+                    dst.assign_from(src);
+                }
+            }
+        }
+    }
+
+    /// Returns mutable references to the fields at the indices in `idx` at the same time.
+    ///
+    /// Returns an error if an index is out of range or appears more than once.
+    /// `#[derive(Fields)]` implements this by splitting the borrow of `self` into its fields,
+    /// including the fields spliced by `#[fields(flatten)]`.
+    /// If a flattened field's type does not use `#[derive(Fields)]`,
+    /// at most one of its fields can be borrowed at a time.
+    ///
+    /// The default implementation supports at most one index,
+    /// and returns [`DisjointError::Unsupported`] for more.
+    fn get_many_mut<const N: usize>(
+        &mut self,
+        idx: [usize; N],
+    ) -> Result<[&mut Self::Item<'_>; N], DisjointError> {
+        __private::check_disjoint(&idx, self.field_len())?;
+        if N > 1 {
+            return Err(DisjointError::Unsupported { position: 1 });
+        }
+        let mut this = Some(self);
+        let items = idx.map(|i| this.take().and_then(|this| this.get_mut(i)));
+        if let Some(position) = items.iter().position(Option::is_none) {
+            return Err(DisjointError::NotFound { position });
+        }
+        Ok(items.map(Option::unwrap))
+    }
+    /// Returns mutable references to the fields of this value named `names` at the same time.
+    ///
+    /// Returns an error if a name is not found or two names refer to the same field.
+    fn get_many_mut_by_name<const N: usize>(
+        &mut self,
+        names: [&str; N],
+    ) -> Result<[&mut Self::Item<'_>; N], DisjointError> {
+        let mut idx = [0; N];
+        for (position, name) in names.iter().enumerate() {
+            idx[position] = self
+                .field_find(name)
+                .ok_or(DisjointError::NotFound { position })?;
+        }
+        self.get_many_mut(idx)
+    }
+
+    fn iter(&self) -> Iter<'_, Self> {
         Iter { s: self, idx: 0 }
     }
-    fn iter_mut(&mut self) -> IterMut<Self> {
+    fn iter_mut(&mut self) -> IterMut<'_, Self> {
         IterMut { s: self, idx: 0 }
     }
-    fn values(&self) -> Values<Self> {
+    fn values(&self) -> Values<'_, Self> {
         Values { s: self, idx: 0 }
     }
-    fn values_mut(&mut self) -> ValuesMut<Self> {
+    fn values_mut(&mut self) -> ValuesMut<'_, Self> {
         ValuesMut { s: self, idx: 0 }
     }
     fn names() -> Names<Self> {
@@ -110,14 +789,239 @@ pub trait Fields: Sized + 'static {
     }
 }
 
+/// Metadata of a field, returned by [`Fields::info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    /// The name of the field in the type that declares it.
+    ///
+    /// Same as [`Fields::name`] except for fields spliced by `#[fields(flatten)]`,
+    /// whose `name` is the name in the inner type without the prefix.
+    pub name: &'static str,
+    /// The type of the field as written in the source.
+    pub ty: &'static str,
+    /// The doc comment of the field, with one line per `///` line.
+    pub doc: &'static str,
+    /// The visibility of the field, such as `"pub"`, `"pub(crate)"` or `""`.
+    pub vis: &'static str,
+    /// The key-value pairs specified by `#[fields(meta(key = "value"))]`.
+    pub meta: &'static [(&'static str, &'static str)],
+}
+
+impl FieldInfo {
+    /// Returns the value of the `#[fields(meta(...))]` entry named `key`.
+    pub fn meta(&self, key: &str) -> Option<&'static str> {
+        self.meta.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+}
+
+/// An error of [`Fields::get_many_mut`] and [`Fields::get_many_mut_by_name`].
+///
+/// Positions are indices into the array passed to the method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisjointError {
+    /// The field at `position` was not found.
+    NotFound { position: usize },
+    /// The field at `position` is the same as the field at `first`.
+    Overlapping { position: usize, first: usize },
+    /// The field at `position` cannot be borrowed together with the fields before it,
+    /// because it belongs to a type that does not split the borrow of its fields.
+    ///
+    /// Returned by the default implementation of [`Fields::get_many_mut`] for more than one index,
+    /// and for indices of a `#[fields(flatten)]` field whose type does not use `#[derive(Fields)]`.
+    Unsupported { position: usize },
+}
+
+impl fmt::Display for DisjointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound { position } => {
+                write!(f, "field at position {} was not found", position)
+            }
+            Self::Overlapping { position, first } => write!(
+                f,
+                "field at position {} is the same as the field at position {}",
+                position, first
+            ),
+            Self::Unsupported { position } => write!(
+                f,
+                "field at position {} cannot be borrowed together with the fields before it",
+                position
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DisjointError {}
+
+/// An interface for access all fields as `I`.
+///
+/// `I` is usually a trait object such as `dyn Debug`.
+/// For structs with lifetime parameters, `I` is `dyn Trait + 'a` for every `'a` the struct outlives.
+///
+/// See the [module-level documentation](index.html) for more details.
+pub trait FieldsAs<I: ?Sized>: Fields {
+    fn get_as(&self, idx: usize) -> Option<&I>;
+    fn get_mut_as(&mut self, idx: usize) -> Option<&mut I>;
+
+    fn iter_as(&self) -> IterAs<'_, Self, I> {
+        IterAs {
+            s: self,
+            idx: 0,
+            _phantom: PhantomData,
+        }
+    }
+    fn iter_mut_as(&mut self) -> IterMutAs<'_, Self, I> {
+        IterMutAs {
+            s: self,
+            idx: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Stores the field at `idx[p] - offset` to `items[p]` for each `p` where `idx[p]` is in `offset..offset + Self::LEN`.
+    ///
+    /// `idx` must not contain the same index twice.
+    /// Used by `#[derive(Fields)]` to split the borrow of a value into its fields, including the fields spliced by `#[fields(flatten)]`.
+    /// The default implementation supports only one such index,
+    /// and returns [`DisjointError::Unsupported`] if there are more.
+    #[doc(hidden)]
+    fn split_mut_as<'s>(
+        &'s mut self,
+        idx: &[usize],
+        offset: usize,
+        items: &mut [Option<&'s mut I>],
+    ) -> Result<(), DisjointError> {
+        let mut ps = (0..idx.len()).filter(|&p| (offset..offset + Self::LEN).contains(&idx[p]));
+        if let Some(p) = ps.next() {
+            if let Some(position) = ps.next() {
+                return Err(DisjointError::Unsupported { position });
+            }
+            items[p] = self.get_mut_as(idx[p] - offset);
+        }
+        Ok(())
+    }
+}
+
 /// An interface for access one field by field type.
 ///
+/// `#[derive(Field)]` implements `Field<T>` only for types used by exactly one field.
+/// Several fields of the same type are allowed so that they can be accessed with [`FieldByTag`],
+/// so the error is reported where `Field<T>` is used rather than by the derive.
+///
 /// See the [module-level documentation](index.html) for more details.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not have exactly one field of type `{T}`",
+    note = "`#[derive(Field)]` does not implement `Field<T>` for types used by several fields; use `FieldByTag` instead"
+)]
 pub trait Field<T> {
     fn get(&self) -> &T;
     fn get_mut(&mut self) -> &mut T;
 }
 
+/// An interface for access one field by a tag type that identifies the field.
+///
+/// `#[derive(Field)]` generates a module `{TypeName}_fields` with a zero-sized tag type per field,
+/// named after the field (`_0`, `_1`, ... for tuple structs).
+///
+/// See the [module-level documentation](index.html) for more details.
+pub trait FieldByTag<Tag> {
+    type Type;
+
+    fn get(&self) -> &Self::Type;
+    fn get_mut(&mut self) -> &mut Self::Type;
+}
+
+/// An interface for find the tag of a field by the [`name_key`] of its name.
+///
+/// `#[derive(Field)]` implements this for each field, keyed by the field's identifier.
+/// Use [`FieldByNameExt::field`] to access the field.
+pub trait FieldByName<const KEY: u64> {
+    type Tag;
+}
+
+fn loose_bytes(name: &str) -> impl Iterator<Item = u8> + '_ {
+    name.bytes()
+        .filter(|&b| b != b'_' && b != b'-')
+        .map(|b| b.to_ascii_lowercase())
+}
+
+/// Returns the key of `name` used by [`FieldByName`].
+///
+/// This is the 64-bit FNV-1a hash of `name`.
+pub const fn name_key(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
+
+/// Methods for access one field by name at compile time.
+///
+/// ```rust
+/// use fieldmap::{name_key, FieldByNameExt};
+///
+/// #[derive(fieldmap::Field)]
+/// struct ExampleType {
+///     value_u8: u8,
+///     value_u16: u16,
+/// }
+///
+/// let x = ExampleType {
+///     value_u8: 100,
+///     value_u16: 200,
+/// };
+/// assert_eq!(*x.field::<{ name_key("value_u8") }>(), 100);
+/// ```
+pub trait FieldByNameExt {
+    fn field<const KEY: u64>(&self) -> &<Self as FieldByTag<<Self as FieldByName<KEY>>::Tag>>::Type
+    where
+        Self: FieldByName<KEY> + FieldByTag<<Self as FieldByName<KEY>>::Tag>,
+    {
+        FieldByTag::<<Self as FieldByName<KEY>>::Tag>::get(self)
+    }
+    fn field_mut<const KEY: u64>(
+        &mut self,
+    ) -> &mut <Self as FieldByTag<<Self as FieldByName<KEY>>::Tag>>::Type
+    where
+        Self: FieldByName<KEY> + FieldByTag<<Self as FieldByName<KEY>>::Tag>,
+    {
+        FieldByTag::<<Self as FieldByName<KEY>>::Tag>::get_mut(self)
+    }
+}
+impl<S: ?Sized> FieldByNameExt for S {}
+
+/// An interface for access one field by field type, which may be absent.
+///
+/// `#[derive(Field)]` implements this for enums.
+/// [`TryField::try_get`] returns `Some` when the active variant has a field of type `T`.
+///
+/// Every type that implements [`Field<T>`] also implements `TryField<T>`.
+///
+/// Like [`Field<T>`], `TryField<T>` is not implemented for types used by several fields of one variant,
+/// and the error is reported where `TryField<T>` is used.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no field of type `{T}`, or has several in one variant",
+    note = "`#[derive(Field)]` does not implement `TryField<T>` for types used by several fields of one variant; match on the variant instead"
+)]
+pub trait TryField<T> {
+    fn try_get(&self) -> Option<&T>;
+    fn try_get_mut(&mut self) -> Option<&mut T>;
+}
+impl<S: Field<T>, T> TryField<T> for S {
+    fn try_get(&self) -> Option<&T> {
+        Some(self.get())
+    }
+    fn try_get_mut(&mut self) -> Option<&mut T> {
+        Some(self.get_mut())
+    }
+}
+
 /// Immutable field iterator of [`Fields`].
 pub struct Iter<'a, S> {
     s: &'a S,
@@ -125,9 +1029,9 @@ pub struct Iter<'a, S> {
 }
 
 impl<'a, S: Fields> Iterator for Iter<'a, S> {
-    type Item = (&'static str, &'a S::Item);
+    type Item = (&'static str, &'a S::Item<'a>);
     fn next(&mut self) -> Option<Self::Item> {
-        if let (Some(name), Some(value)) = (S::name(self.idx), self.s.get(self.idx)) {
+        if let (Some(name), Some(value)) = (self.s.field_name(self.idx), self.s.get(self.idx)) {
             self.idx += 1;
             Some((name, value))
         } else {
@@ -135,7 +1039,7 @@ impl<'a, S: Fields> Iterator for Iter<'a, S> {
         }
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = S::len() - self.idx;
+        let size = self.s.field_len() - self.idx;
         (size, Some(size))
     }
 }
@@ -149,30 +1053,83 @@ pub struct IterMut<'a, S> {
 }
 
 impl<'a, S: Fields> Iterator for IterMut<'a, S> {
-    type Item = (&'static str, &'a mut S::Item);
+    type Item = (&'static str, &'a mut S::Item<'a>);
     fn next(&mut self) -> Option<Self::Item> {
-        if let (Some(name), Some(value)) = (S::name(self.idx), self.s.get_mut(self.idx)) {
+        if let (Some(name), Some(value)) = (self.s.field_name(self.idx), self.s.get_mut(self.idx)) {
             self.idx += 1;
-            Some((name, unsafe { ::core::mem::transmute(value) }))
+            Some((name, unsafe { extend_mut::<S>(value) }))
         } else {
             None
         }
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = S::len() - self.idx;
+        let size = self.s.field_len() - self.idx;
         (size, Some(size))
     }
 }
 impl<'a, S: Fields> ExactSizeIterator for IterMut<'a, S> {}
 impl<'a, S: Fields> FusedIterator for IterMut<'a, S> {}
 
+/// Immutable field iterator of [`FieldsAs`].
+pub struct IterAs<'a, S, I: ?Sized> {
+    s: &'a S,
+    idx: usize,
+    _phantom: PhantomData<fn() -> &'a I>,
+}
+
+impl<'a, S: FieldsAs<I>, I: ?Sized> Iterator for IterAs<'a, S, I> {
+    type Item = (&'static str, &'a I);
+    fn next(&mut self) -> Option<Self::Item> {
+        if let (Some(name), Some(value)) = (self.s.field_name(self.idx), self.s.get_as(self.idx)) {
+            self.idx += 1;
+            Some((name, value))
+        } else {
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.s.field_len() - self.idx;
+        (size, Some(size))
+    }
+}
+impl<'a, S: FieldsAs<I>, I: ?Sized> ExactSizeIterator for IterAs<'a, S, I> {}
+impl<'a, S: FieldsAs<I>, I: ?Sized> FusedIterator for IterAs<'a, S, I> {}
+
+/// Mutable field iterator of [`FieldsAs`].
+pub struct IterMutAs<'a, S, I: ?Sized> {
+    s: &'a mut S,
+    idx: usize,
+    _phantom: PhantomData<fn() -> &'a mut I>,
+}
+
+impl<'a, S: FieldsAs<I>, I: ?Sized> Iterator for IterMutAs<'a, S, I> {
+    type Item = (&'static str, &'a mut I);
+    fn next(&mut self) -> Option<Self::Item> {
+        if let (Some(name), Some(value)) =
+            (self.s.field_name(self.idx), self.s.get_mut_as(self.idx))
+        {
+            self.idx += 1;
+            // Each index is yielded at most once, so the returned references never alias.
+            Some((name, unsafe { &mut *(value as *mut I) }))
+        } else {
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.s.field_len() - self.idx;
+        (size, Some(size))
+    }
+}
+impl<'a, S: FieldsAs<I>, I: ?Sized> ExactSizeIterator for IterMutAs<'a, S, I> {}
+impl<'a, S: FieldsAs<I>, I: ?Sized> FusedIterator for IterMutAs<'a, S, I> {}
+
 pub struct Values<'a, S> {
     s: &'a S,
     idx: usize,
 }
 
 impl<'a, M: Fields> Iterator for Values<'a, M> {
-    type Item = &'a M::Item;
+    type Item = &'a M::Item<'a>;
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(value) = self.s.get(self.idx) {
             self.idx += 1;
@@ -182,7 +1139,7 @@ impl<'a, M: Fields> Iterator for Values<'a, M> {
         }
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = M::len() - self.idx;
+        let size = self.s.field_len() - self.idx;
         (size, Some(size))
     }
 }
@@ -195,23 +1152,32 @@ pub struct ValuesMut<'a, S> {
 }
 
 impl<'a, S: Fields> Iterator for ValuesMut<'a, S> {
-    type Item = &'a mut S::Item;
+    type Item = &'a mut S::Item<'a>;
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(value) = self.s.get_mut(self.idx) {
             self.idx += 1;
-            Some(unsafe { ::core::mem::transmute(value) })
+            Some(unsafe { extend_mut::<S>(value) })
         } else {
             None
         }
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = S::len() - self.idx;
+        let size = self.s.field_len() - self.idx;
         (size, Some(size))
     }
 }
 impl<'a, S: Fields> ExactSizeIterator for ValuesMut<'a, S> {}
 impl<'a, S: Fields> FusedIterator for ValuesMut<'a, S> {}
 
+/// Extends the lifetime of a value borrowed from the iterator's `&'a mut S`.
+///
+/// # Safety
+///
+/// Each index must be yielded at most once, so the returned references never alias.
+unsafe fn extend_mut<'a, 'b, S: Fields + 'a>(value: &'b mut S::Item<'b>) -> &'a mut S::Item<'a> {
+    ::core::mem::transmute::<&'b mut S::Item<'b>, &'a mut S::Item<'a>>(value)
+}
+
 pub struct Names<S> {
     idx: usize,
     _phantom: PhantomData<fn(&S)>,
@@ -234,3 +1200,185 @@ impl<S: Fields> Iterator for Names<S> {
 }
 impl<S: Fields> ExactSizeIterator for Names<S> {}
 impl<S: Fields> FusedIterator for Names<S> {}
+
+/// Items used by the code generated by `#[derive(Fields)]`. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use crate::DisjointError;
+
+    /// Checks that `idx` are distinct indices less than `len`.
+    pub fn check_disjoint(idx: &[usize], len: usize) -> Result<(), DisjointError> {
+        for (position, &i) in idx.iter().enumerate() {
+            if i >= len {
+                return Err(DisjointError::NotFound { position });
+            }
+            if let Some(first) = idx[..position].iter().position(|&x| x == i) {
+                return Err(DisjointError::Overlapping { position, first });
+            }
+        }
+        Ok(())
+    }
+
+    /// A prefix and the field names it applies to.
+    pub type NameGroup = (&'static str, &'static [&'static str]);
+
+    pub const fn names_len(groups: &[NameGroup]) -> usize {
+        let mut len = 0;
+        let mut g = 0;
+        while g < groups.len() {
+            len += groups[g].1.len();
+            g += 1;
+        }
+        len
+    }
+    pub const fn names_bytes_len(groups: &[NameGroup]) -> usize {
+        let mut len = 0;
+        let mut g = 0;
+        while g < groups.len() {
+            let (prefix, names) = groups[g];
+            let mut i = 0;
+            while i < names.len() {
+                len += prefix.len() + names[i].len();
+                i += 1;
+            }
+            g += 1;
+        }
+        len
+    }
+
+    /// Concatenates every name in `groups` with its prefix.
+    pub const fn names_bytes<const N: usize>(groups: &[NameGroup]) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        let mut p = 0;
+        let mut g = 0;
+        while g < groups.len() {
+            let (prefix, names) = groups[g];
+            let mut i = 0;
+            while i < names.len() {
+                p = copy(&mut bytes, p, prefix.as_bytes());
+                p = copy(&mut bytes, p, names[i].as_bytes());
+                i += 1;
+            }
+            g += 1;
+        }
+        bytes
+    }
+    const fn copy(dst: &mut [u8], mut p: usize, src: &[u8]) -> usize {
+        let mut i = 0;
+        while i < src.len() {
+            dst[p] = src[i];
+            p += 1;
+            i += 1;
+        }
+        p
+    }
+
+    /// Splits `bytes` created by [`names_bytes`] into names.
+    ///
+    /// Panics if a name appears more than once,
+    /// or if two names are the same when ASCII case, `_` and `-` are ignored as [`Fields::find_loose`](crate::Fields::find_loose) does.
+    pub const fn names<const N: usize>(
+        mut bytes: &'static [u8],
+        groups: &[NameGroup],
+    ) -> [&'static str; N] {
+        let mut names = [""; N];
+        let mut n = 0;
+        let mut g = 0;
+        while g < groups.len() {
+            let (prefix, group) = groups[g];
+            let mut i = 0;
+            while i < group.len() {
+                let (name, rest) = bytes.split_at(prefix.len() + group[i].len());
+                names[n] = match core::str::from_utf8(name) {
+                    Ok(name) => name,
+                    Err(_) => panic!("invalid field name"),
+                };
+                let mut j = 0;
+                while j < n {
+                    if eq(names[j].as_bytes(), name) {
+                        panic!("duplicate field name");
+                    }
+                    if loose_eq(names[j].as_bytes(), name) {
+                        panic!("field names are the same when case, `_` and `-` are ignored");
+                    }
+                    j += 1;
+                }
+                bytes = rest;
+                n += 1;
+                i += 1;
+            }
+            g += 1;
+        }
+        names
+    }
+    const fn loose_eq(a: &[u8], b: &[u8]) -> bool {
+        let mut i = 0;
+        let mut j = 0;
+        loop {
+            while i < a.len() && (a[i] == b'_' || a[i] == b'-') {
+                i += 1;
+            }
+            while j < b.len() && (b[j] == b'_' || b[j] == b'-') {
+                j += 1;
+            }
+            if i == a.len() || j == b.len() {
+                return i == a.len() && j == b.len();
+            }
+            if !a[i].eq_ignore_ascii_case(&b[j]) {
+                return false;
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+    const fn eq(a: &[u8], b: &[u8]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Writes `name` to `buf` with ASCII letters lowercased and `_` and `-` removed, as [`Fields::find_loose`](crate::Fields::find_loose) compares names.
+    ///
+    /// Returns `None` if `buf` is too short.
+    pub fn normalize_name<'a>(name: &str, buf: &'a mut [u8]) -> Option<&'a [u8]> {
+        let mut len = 0;
+        for b in crate::loose_bytes(name) {
+            *buf.get_mut(len)? = b;
+            len += 1;
+        }
+        Some(&buf[..len])
+    }
+
+    /// Removes `prefix` from `name` ignoring ASCII case, `_` and `-` as [`Fields::find_loose`](crate::Fields::find_loose) does.
+    ///
+    /// `prefix` must be normalized by [`normalize_name`].
+    pub fn strip_prefix_loose<'a>(name: &'a str, prefix: &str) -> Option<&'a str> {
+        let prefix = prefix.as_bytes();
+        let mut p = 0;
+        for (i, b) in name.bytes().enumerate() {
+            if p == prefix.len() {
+                return name.get(i..);
+            }
+            if b == b'_' || b == b'-' {
+                continue;
+            }
+            if b.to_ascii_lowercase() != prefix[p] {
+                return None;
+            }
+            p += 1;
+        }
+        if p == prefix.len() {
+            Some("")
+        } else {
+            None
+        }
+    }
+}
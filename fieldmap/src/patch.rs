@@ -0,0 +1,173 @@
+use core::any::Any;
+
+#[cfg(feature = "alloc")]
+use crate::{Fields, FieldsAs};
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A value that can be overwritten by a copy of a value of unknown type.
+///
+/// This trait is object safe and implemented for all types that implement [`Clone`] and [`Any`].
+/// Use `#[fields(item = MergeItem)]` to make a type usable with [`Fields::merge_from`](crate::Fields::merge_from)
+/// and `Patch`.
+pub trait MergeItem {
+    #[doc(hidden)]
+    fn merge_any(&self) -> &dyn Any;
+    /// Assigns a clone of `other` to `self` and returns `true` if `other` is of the same type as `self`.
+    ///
+    /// Returns `false` and leaves `self` unchanged otherwise.
+    fn assign_from(&mut self, other: &dyn MergeItem) -> bool;
+}
+
+impl<T: Clone + Any> MergeItem for T {
+    fn merge_any(&self) -> &dyn Any {
+        self
+    }
+    fn assign_from(&mut self, other: &dyn MergeItem) -> bool {
+        if let Some(other) = other.merge_any().downcast_ref::<T>() {
+            self.clone_from(other);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A partial update of a [`Fields`] value, keyed by field index.
+///
+/// A patch holds a value of `T` and the set of fields that are set.
+/// [`Patch::apply`] copies the fields that are set to another value, and the other fields are ignored.
+/// With the `serde` feature, a patch serializes as a map of the fields that are set,
+/// and deserializes from a map that may omit any field.
+///
+/// ```rust
+/// use fieldmap::{Fields, MergeItem, Patch};
+///
+/// #[derive(Fields, Default)]
+/// #[fields(item = MergeItem)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let mut patch = Patch::<Config>::default();
+/// patch.set(Config::find("port").unwrap(), &8080u16);
+///
+/// let mut config = Config { host: "localhost".into(), port: 80 };
+/// patch.apply(&mut config);
+/// assert_eq!(config.host, "localhost");
+/// assert_eq!(config.port, 8080);
+/// ```
+///
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Patch<T> {
+    value: T,
+    set: Vec<bool>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Fields> Patch<T> {
+    /// Creates a patch with no fields set, which uses `value` to store the fields that will be set.
+    pub fn new(value: T) -> Self {
+        let set = vec![false; value.field_len()];
+        Self { value, set }
+    }
+    /// Creates a patch with all fields of `value` set.
+    pub fn all(value: T) -> Self {
+        let set = vec![true; value.field_len()];
+        Self { value, set }
+    }
+
+    /// Returns the value that stores the fields.
+    ///
+    /// Fields that are not set have the values given to [`Patch::new`].
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+    /// Returns `true` if the field at `idx` is set.
+    pub fn is_set(&self, idx: usize) -> bool {
+        self.set.get(idx).copied().unwrap_or(false)
+    }
+    /// Returns the number of fields that are set.
+    pub fn len(&self) -> usize {
+        self.set.iter().filter(|s| **s).count()
+    }
+    /// Returns `true` if no fields are set.
+    pub fn is_empty(&self) -> bool {
+        !self.set.contains(&true)
+    }
+    /// Returns the indices of the fields that are set, in index order.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.set
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| **s)
+            .map(|(idx, _)| idx)
+    }
+
+    /// Returns the field at `idx` if it is set.
+    pub fn get(&self, idx: usize) -> Option<&T::Item<'_>> {
+        if self.is_set(idx) {
+            self.value.get(idx)
+        } else {
+            None
+        }
+    }
+    /// Returns the field at `idx` and marks it as set.
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T::Item<'_>> {
+        let value = self.value.get_mut(idx)?;
+        self.set[idx] = true;
+        Some(value)
+    }
+    /// Sets the field at `idx` to a clone of `value`.
+    ///
+    /// Returns `false` and leaves the patch unchanged if there is no such field or `value` is not of the type of the field.
+    pub fn set<'a>(&mut self, idx: usize, value: &dyn MergeItem) -> bool
+    where
+        T: FieldsAs<dyn MergeItem + 'a>,
+    {
+        let Some(item) = self.value.get_mut_as(idx) else {
+            return false;
+        };
+        if item.assign_from(value) {
+            self.set[idx] = true;
+            true
+        } else {
+            false
+        }
+    }
+    /// Unsets the field at `idx`.
+    pub fn unset(&mut self, idx: usize) {
+        if let Some(s) = self.set.get_mut(idx) {
+            *s = false;
+        }
+    }
+
+    /// Copies the fields that are set to `target`.
+    pub fn apply<'a>(&self, target: &mut T)
+    where
+        T: FieldsAs<dyn MergeItem + 'a>,
+    {
+        target.merge_from(&self.value, |idx, _| self.is_set(idx));
+    }
+
+    /// Returns the value that stores the fields, consuming the patch.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+    #[cfg(feature = "serde")]
+    pub(crate) fn parts_mut(&mut self) -> (&mut T, &mut Vec<bool>) {
+        (&mut self.value, &mut self.set)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Fields + Default> Default for Patch<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::FieldsAs;
+use core::any::Any;
+use core::fmt;
+
+/// A value that can be walked into with a dotted path such as `"a.b.0.c"`.
+///
+/// Use `#[fields(item = PathItem)]` to make a type that implements [`Fields`](crate::Fields) walkable.
+/// Such a type also implements `PathItem` itself, so it can be nested.
+/// `PathItem` is also implemented for primitive types, `&'static str`, `String`, arrays and `Vec` of `PathItem`.
+///
+/// See [`FieldsPath`] for the methods to access values by path.
+pub trait PathItem {
+    /// Returns the child named `segment`, or `None` if there is no such child.
+    fn path_child(&self, segment: &str) -> Option<&dyn PathItem>;
+    /// Returns the mutable child named `segment`, or `None` if there is no such child.
+    fn path_child_mut(&mut self, segment: &str) -> Option<&mut dyn PathItem>;
+    /// Calls `f` for each child in order.
+    fn path_children(&self, f: &mut dyn FnMut(Segment, &dyn PathItem));
+    /// Returns `self` as `dyn Any` for downcasting.
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl dyn PathItem + '_ {
+    /// Returns the value as `T` if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+    /// Returns the mutable value as `T` if it is of type `T`.
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+impl<S> PathItem for S
+where
+    S: Any + FieldsAs<dyn PathItem>,
+{
+    fn path_child(&self, segment: &str) -> Option<&dyn PathItem> {
+        self.get_as(self.field_find(segment)?)
+    }
+    fn path_child_mut(&mut self, segment: &str) -> Option<&mut dyn PathItem> {
+        let idx = self.field_find(segment)?;
+        let value: &mut dyn PathItem = self.get_mut_as(idx)?;
+        Some(value)
+    }
+    fn path_children(&self, f: &mut dyn FnMut(Segment, &dyn PathItem)) {
+        for (name, value) in self.iter_as() {
+            f(Segment::Name(name), value);
+        }
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+macro_rules! impl_path_item_leaf {
+    ($($t:ty),*) => {
+        $(
+            impl PathItem for $t {
+                fn path_child(&self, _segment: &str) -> Option<&dyn PathItem> {
+                    None
+                }
+                fn path_child_mut(&mut self, _segment: &str) -> Option<&mut dyn PathItem> {
+                    None
+                }
+                fn path_children(&self, _f: &mut dyn FnMut(Segment, &dyn PathItem)) {}
+                fn as_any(&self) -> &dyn Any {
+                    self
+                }
+                fn as_any_mut(&mut self) -> &mut dyn Any {
+                    self
+                }
+            }
+        )*
+    };
+}
+impl_path_item_leaf!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    &'static str
+);
+#[cfg(feature = "alloc")]
+impl_path_item_leaf!(alloc::string::String);
+
+macro_rules! impl_path_item_seq {
+    ($($g:tt)*) => {
+        $($g)* {
+            fn path_child(&self, segment: &str) -> Option<&dyn PathItem> {
+                Some(self.get(segment.parse::<usize>().ok()?)?)
+            }
+            fn path_child_mut(&mut self, segment: &str) -> Option<&mut dyn PathItem> {
+                Some(self.get_mut(segment.parse::<usize>().ok()?)?)
+            }
+            fn path_children(&self, f: &mut dyn FnMut(Segment, &dyn PathItem)) {
+                for (idx, value) in self.iter().enumerate() {
+                    f(Segment::Index(idx), value);
+                }
+            }
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+            fn as_any_mut(&mut self) -> &mut dyn Any {
+                self
+            }
+        }
+    };
+}
+impl_path_item_seq!(impl<T: PathItem + 'static, const N: usize> PathItem for [T; N]);
+#[cfg(feature = "alloc")]
+impl_path_item_seq!(impl<T: PathItem + 'static> PathItem for alloc::vec::Vec<T>);
+
+/// A segment of a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// A field name.
+    Name(&'static str),
+    /// An index of an array or a `Vec`.
+    Index(usize),
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Segment::Name(name) => write!(f, "{}", name),
+            Segment::Index(idx) => write!(f, "{}", idx),
+        }
+    }
+}
+
+/// The path from the root to a value visited by [`FieldsPath::walk`] or [`Visit::visit`](crate::Visit::visit).
+///
+/// Displayed with `.` between segments, such as `a.b.0.c`.
+#[derive(Debug, Clone, Copy)]
+pub struct Path<'a> {
+    parent: Option<&'a Path<'a>>,
+    segment: Segment,
+}
+
+impl<'a> Path<'a> {
+    /// Creates a path that appends `segment` to `parent`.
+    pub fn new(parent: Option<&'a Path<'a>>, segment: Segment) -> Self {
+        Self { parent, segment }
+    }
+}
+
+impl Path<'_> {
+    /// Returns the last segment.
+    pub fn segment(&self) -> Segment {
+        self.segment
+    }
+    /// Returns the path without the last segment, or `None` if this path has only one segment.
+    pub fn parent(&self) -> Option<&Path<'_>> {
+        self.parent
+    }
+    /// Returns the number of segments.
+    pub fn depth(&self) -> usize {
+        self.parent.map_or(0, |p| p.depth()) + 1
+    }
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(parent) = self.parent {
+            write!(f, "{}.", parent)?;
+        }
+        write!(f, "{}", self.segment)
+    }
+}
+
+/// An error of [`FieldsPath::get_path`] that the segment at [`PathError::index`] was not found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathError<'a> {
+    path: &'a str,
+    index: usize,
+    segment: &'a str,
+}
+
+impl<'a> PathError<'a> {
+    /// Returns the whole path.
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+    /// Returns the zero-based position of the segment that was not found.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// Returns the segment that was not found.
+    pub fn segment(&self) -> &'a str {
+        self.segment
+    }
+}
+
+impl fmt::Display for PathError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "`{}` (segment {}) of path `{}` was not found",
+            self.segment, self.index, self.path
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PathError<'_> {}
+
+/// Methods for access values in nested [`PathItem`]s by dotted path.
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use fieldmap::{Fields, FieldsPath, PathItem};
+///
+/// #[derive(Fields)]
+/// #[fields(item = PathItem)]
+/// struct Point(u32, u32);
+///
+/// #[derive(Fields)]
+/// #[fields(item = PathItem)]
+/// struct Shape {
+///     points: Vec<Point>,
+/// }
+///
+/// let mut x = Shape {
+///     points: vec![Point(1, 2), Point(3, 4)],
+/// };
+/// let value = x.get_path("points.1.0").unwrap();
+/// assert_eq!(value.downcast_ref::<u32>(), Some(&3));
+///
+/// *x.get_path_mut("points.0.1").unwrap().downcast_mut::<u32>().unwrap() = 5;
+/// assert_eq!(x.points[0].1, 5);
+///
+/// let e = x.get_path("points.2.0").err().unwrap();
+/// assert_eq!(e.segment(), "2");
+/// # }
+/// ```
+pub trait FieldsPath: PathItem {
+    /// Returns the value at `path`.
+    fn get_path<'a>(&self, path: &'a str) -> Result<&dyn PathItem, PathError<'a>> {
+        let mut value: &dyn PathItem = self.as_path_item();
+        for (index, segment) in path.split('.').enumerate() {
+            value = value.path_child(segment).ok_or(PathError {
+                path,
+                index,
+                segment,
+            })?;
+        }
+        Ok(value)
+    }
+    /// Returns the mutable value at `path`.
+    fn get_path_mut<'a>(&mut self, path: &'a str) -> Result<&mut dyn PathItem, PathError<'a>> {
+        let mut value: &mut dyn PathItem = self.as_path_item_mut();
+        for (index, segment) in path.split('.').enumerate() {
+            value = value.path_child_mut(segment).ok_or(PathError {
+                path,
+                index,
+                segment,
+            })?;
+        }
+        Ok(value)
+    }
+    /// Calls `f` for every descendant in depth-first order, with its path from `self`.
+    fn walk(&self, f: &mut dyn FnMut(&Path, &dyn PathItem)) {
+        walk(self.as_path_item(), None, f);
+    }
+
+    #[doc(hidden)]
+    fn as_path_item(&self) -> &dyn PathItem;
+    #[doc(hidden)]
+    fn as_path_item_mut(&mut self) -> &mut dyn PathItem;
+}
+impl<T: PathItem> FieldsPath for T {
+    fn as_path_item(&self) -> &dyn PathItem {
+        self
+    }
+    fn as_path_item_mut(&mut self) -> &mut dyn PathItem {
+        self
+    }
+}
+
+fn walk(value: &dyn PathItem, parent: Option<&Path>, f: &mut dyn FnMut(&Path, &dyn PathItem)) {
+    value.path_children(&mut |segment, value| {
+        let path = Path { parent, segment };
+        f(&path, value);
+        walk(value, Some(&path), f);
+    });
+}
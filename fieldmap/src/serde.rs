@@ -0,0 +1,232 @@
+//! Serializing and deserializing [`Fields`] as maps of name to value.
+//!
+//! Requires the `serde` feature.
+use crate::{Fields, FieldsAs, Patch};
+use ::serde::de::{self, Deserialize, DeserializeOwned, DeserializeSeed, MapAccess};
+use ::serde::ser::{SerializeMap, Serializer};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+pub use erased_serde;
+
+/// Serializes a [`Fields`] value as a map of field name to value.
+///
+/// `Fields::Item` must implement [`erased_serde::Serialize`],
+/// for example with `#[fields(item = erased_serde::Serialize)]`.
+///
+/// For enums, the map contains the fields of the active variant.
+///
+/// See also [`Patch`], which serializes only the fields that are set.
+#[derive(Debug, Clone, Copy)]
+pub struct AsMap<T>(pub T);
+
+impl<'a, T> ::serde::Serialize for AsMap<&'a T>
+where
+    T: Fields,
+    T::Item<'a>: erased_serde::Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.field_len()))?;
+        for (name, value) in self.0.iter() {
+            map.serialize_entry(name, &Erased(value))?;
+        }
+        map.end()
+    }
+}
+
+struct Erased<'a, I: ?Sized>(&'a I);
+
+impl<I: ?Sized + erased_serde::Serialize> ::serde::Serialize for Erased<'_, I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        erased_serde::serialize(self.0, serializer)
+    }
+}
+
+/// A value that can be overwritten by deserializing into it.
+///
+/// This trait is object safe and implemented for all types that implement [`DeserializeOwned`].
+/// Use `#[fields(item = DeserializeItem)]` to make a type usable with [`deserialize_fields`].
+pub trait DeserializeItem {
+    /// Replaces `self` with the value read from `deserializer`.
+    fn deserialize_item(
+        &mut self,
+        deserializer: &mut dyn erased_serde::Deserializer<'_>,
+    ) -> Result<(), erased_serde::Error>;
+}
+
+impl<T: DeserializeOwned> DeserializeItem for T {
+    fn deserialize_item(
+        &mut self,
+        deserializer: &mut dyn erased_serde::Deserializer<'_>,
+    ) -> Result<(), erased_serde::Error> {
+        *self = erased_serde::deserialize(deserializer)?;
+        Ok(())
+    }
+}
+
+/// Fills the fields of `value` from a map of field name to value read from `deserializer`.
+///
+/// Keys are looked up with [`Fields::field_find`], so aliases are accepted.
+/// Unknown keys, duplicate keys and fields missing from the map are reported as errors.
+/// Fields are assigned as they are read, so `value` may be partially updated when an error occurs.
+pub fn deserialize_fields<'de, 'a, T, D>(value: &mut T, deserializer: D) -> Result<(), D::Error>
+where
+    T: FieldsAs<dyn DeserializeItem + 'a>,
+    D: de::Deserializer<'de>,
+{
+    let mut seen = vec![false; value.field_len()];
+    FieldsSeed {
+        value,
+        seen: &mut seen,
+        partial: false,
+        _phantom: PhantomData,
+    }
+    .deserialize(deserializer)
+}
+
+struct FieldsSeed<'s, 'a, T> {
+    value: &'s mut T,
+    seen: &'s mut [bool],
+    partial: bool,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<'de, 'a, T> DeserializeSeed<'de> for FieldsSeed<'_, 'a, T>
+where
+    T: FieldsAs<dyn DeserializeItem + 'a>,
+{
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a, T> de::Visitor<'de> for FieldsSeed<'_, 'a, T>
+where
+    T: FieldsAs<dyn DeserializeItem + 'a>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of field names to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let value = self.value;
+        let seen = self.seen;
+        while let Some(idx) = map.next_key_seed(KeySeed(&*value))? {
+            if seen[idx] {
+                let name = value.field_name(idx).unwrap_or_default();
+                return Err(de::Error::duplicate_field(name));
+            }
+            seen[idx] = true;
+            let item: &mut (dyn DeserializeItem + 'a) = value.get_mut_as(idx).unwrap();
+            map.next_value_seed(ItemSeed(item))?;
+        }
+        if self.partial {
+            return Ok(());
+        }
+        let missing: Vec<&str> = (0..seen.len())
+            .filter(|&idx| !seen[idx])
+            .filter_map(|idx| value.field_name(idx))
+            .collect();
+        match missing[..] {
+            [] => Ok(()),
+            [name] => Err(de::Error::missing_field(name)),
+            _ => {
+                let mut names = String::new();
+                for name in missing {
+                    if !names.is_empty() {
+                        names.push_str(", ");
+                    }
+                    names.push('`');
+                    names.push_str(name);
+                    names.push('`');
+                }
+                Err(de::Error::custom(format_args!("missing fields {names}")))
+            }
+        }
+    }
+}
+
+struct KeySeed<'s, T>(&'s T);
+
+impl<'de, T: Fields> DeserializeSeed<'de> for KeySeed<'_, T> {
+    type Value = usize;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, T: Fields> de::Visitor<'de> for KeySeed<'_, T> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a field name")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+        self.0
+            .field_find(v)
+            .ok_or_else(|| E::unknown_field(v, T::FIELD_NAMES))
+    }
+}
+
+struct ItemSeed<'s, I: ?Sized>(&'s mut I);
+
+impl<'de, I: ?Sized + DeserializeItem> DeserializeSeed<'de> for ItemSeed<'_, I> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        let mut deserializer = <dyn erased_serde::Deserializer>::erase(deserializer);
+        self.0
+            .deserialize_item(&mut deserializer)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Serializes the fields that are set as a map of field name to value.
+///
+/// Requires `#[fields(item = erased_serde::Serialize)]`.
+impl<T> ::serde::Serialize for Patch<T>
+where
+    T: FieldsAs<dyn erased_serde::Serialize>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for idx in self.indices() {
+            let value = self.value();
+            if let (Some(name), Some(item)) = (value.field_name(idx), value.get_as(idx)) {
+                map.serialize_entry(name, &Erased(item))?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// Deserializes a map of field name to value into a patch with the fields in the map set.
+///
+/// Unknown keys and duplicate keys are reported as errors, and missing fields are left unset.
+/// Requires `#[fields(item = DeserializeItem)]`.
+impl<'de, T> Deserialize<'de> for Patch<T>
+where
+    T: Default + FieldsAs<dyn DeserializeItem>,
+{
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut patch = Patch::default();
+        let (value, seen) = patch.parts_mut();
+        FieldsSeed {
+            value,
+            seen,
+            partial: true,
+            _phantom: PhantomData,
+        }
+        .deserialize(deserializer)?;
+        Ok(patch)
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{Fields, IterMut};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Deref;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// `Send` and `Sync` so that the auto traits of `Tracked` do not depend on the `alloc` feature.
+#[cfg(feature = "alloc")]
+type OnChange = Box<dyn FnMut(usize, &'static str) + Send + Sync>;
+
+/// A wrapper of a [`Fields`] value that records which fields were accessed mutably.
+///
+/// Mutable access is available only through [`Tracked::get_mut`] and [`Tracked::iter_mut`],
+/// which mark the fields they return as dirty. Shared access is available through `Deref`.
+///
+/// The dirty bits are stored in `[u64; WORDS]`, so `Tracked` does not allocate.
+/// `WORDS` defaults to 1, which supports types with up to 64 fields.
+/// For types with more fields, create the wrapper with [`Tracked::new_wide`] and a larger `WORDS`.
+/// Using a type with more than `WORDS * 64` fields is a compile-time error for structs,
+/// and a panic in [`Tracked::new`] and [`Tracked::new_wide`] for enums.
+///
+/// ```rust
+/// use fieldmap::{Fields, Tracked};
+/// use std::fmt::Debug;
+///
+/// #[derive(Fields)]
+/// #[fields(item = Debug)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let mut x = Tracked::new(Config { host: "localhost".into(), port: 80 });
+/// x.get_mut(1).unwrap();
+/// assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["port"]);
+/// x.clear_dirty();
+/// assert!(!x.is_dirty(1));
+/// ```
+pub struct Tracked<T, const WORDS: usize = 1> {
+    value: T,
+    dirty: [u64; WORDS],
+    #[cfg(feature = "alloc")]
+    on_change: Option<OnChange>,
+}
+
+impl<T: Fields> Tracked<T> {
+    /// Wraps `value` with no fields marked as dirty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` has more than 64 fields.
+    pub fn new(value: T) -> Self {
+        Self::new_wide(value)
+    }
+}
+
+impl<T: Fields, const WORDS: usize> Tracked<T, WORDS> {
+    const CAPACITY_CHECK: () = assert!(
+        T::LEN <= WORDS * 64,
+        "the type has more fields than `Tracked` can track, increase `WORDS`"
+    );
+
+    /// Wraps `value` with no fields marked as dirty, using `WORDS` words for the dirty bits.
+    ///
+    /// ```rust
+    /// # use fieldmap::{Fields, Tracked};
+    /// # #[derive(Fields)]
+    /// # #[fields(item = std::fmt::Debug)]
+    /// # struct Wide {
+    /// #     value: u8,
+    /// # }
+    /// let x = Tracked::<_, 2>::new_wide(Wide { value: 0 });
+    /// # assert!(!x.is_any_dirty());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` has more than `WORDS * 64` fields.
+    pub fn new_wide(value: T) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CAPACITY_CHECK;
+        assert!(
+            value.field_len() <= WORDS * 64,
+            "the value has more fields than `Tracked` can track, increase `WORDS`"
+        );
+        Self {
+            value,
+            dirty: [0; WORDS],
+            #[cfg(feature = "alloc")]
+            on_change: None,
+        }
+    }
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the field at `idx` and marks it as dirty.
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T::Item<'_>> {
+        let name = self.value.field_name(idx)?;
+        mark(
+            &mut self.dirty,
+            #[cfg(feature = "alloc")]
+            &mut self.on_change,
+            idx,
+            name,
+        );
+        self.value.get_mut(idx)
+    }
+    /// Returns an iterator over the fields that marks each field as dirty when it is returned.
+    pub fn iter_mut(&mut self) -> TrackedIterMut<'_, T, WORDS> {
+        TrackedIterMut {
+            iter: self.value.iter_mut(),
+            idx: 0,
+            dirty: &mut self.dirty,
+            #[cfg(feature = "alloc")]
+            on_change: &mut self.on_change,
+        }
+    }
+
+    /// Marks the field at `idx` as dirty.
+    pub fn mark_dirty(&mut self, idx: usize) {
+        if let Some(name) = self.value.field_name(idx) {
+            mark(
+                &mut self.dirty,
+                #[cfg(feature = "alloc")]
+                &mut self.on_change,
+                idx,
+                name,
+            );
+        }
+    }
+    /// Returns `true` if the field at `idx` is dirty.
+    pub fn is_dirty(&self, idx: usize) -> bool {
+        idx < WORDS * 64 && self.dirty[idx / 64] & (1 << (idx % 64)) != 0
+    }
+    /// Returns `true` if any field is dirty.
+    pub fn is_any_dirty(&self) -> bool {
+        self.dirty.iter().any(|w| *w != 0)
+    }
+    /// Returns the indices of the dirty fields, in index order.
+    pub fn dirty(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.value.field_len()).filter(|&idx| self.is_dirty(idx))
+    }
+    /// Returns the names of the dirty fields, in index order.
+    pub fn dirty_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.dirty().filter_map(|idx| self.value.field_name(idx))
+    }
+    /// Marks all fields as clean.
+    pub fn clear_dirty(&mut self) {
+        self.dirty = [0; WORDS];
+    }
+
+    /// Sets a function that is called with the index and the name of a field each time the field is marked as dirty.
+    ///
+    /// The function must be `Send` and `Sync` so that `Tracked` is `Send` and `Sync` regardless of features.
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn set_on_change(&mut self, f: impl FnMut(usize, &'static str) + Send + Sync + 'static) {
+        self.on_change = Some(Box::new(f));
+    }
+}
+
+impl<T, const WORDS: usize> Deref for Tracked<T, WORDS> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Debug, const WORDS: usize> fmt::Debug for Tracked<T, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tracked")
+            .field("value", &self.value)
+            .field("dirty", &self.dirty)
+            .finish_non_exhaustive()
+    }
+}
+
+fn mark<const WORDS: usize>(
+    dirty: &mut [u64; WORDS],
+    #[cfg(feature = "alloc")] on_change: &mut Option<OnChange>,
+    idx: usize,
+    name: &'static str,
+) {
+    dirty[idx / 64] |= 1 << (idx % 64);
+    #[cfg(feature = "alloc")]
+    if let Some(f) = on_change {
+        f(idx, name);
+    }
+    #[cfg(not(feature = "alloc"))]
+    let _ = name;
+}
+
+/// Mutable field iterator of [`Tracked`].
+pub struct TrackedIterMut<'a, T, const WORDS: usize> {
+    iter: IterMut<'a, T>,
+    idx: usize,
+    dirty: &'a mut [u64; WORDS],
+    #[cfg(feature = "alloc")]
+    on_change: &'a mut Option<OnChange>,
+}
+
+impl<'a, T: Fields, const WORDS: usize> Iterator for TrackedIterMut<'a, T, WORDS> {
+    type Item = (&'static str, &'a mut T::Item<'a>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (name, value) = self.iter.next()?;
+        mark(
+            self.dirty,
+            #[cfg(feature = "alloc")]
+            self.on_change,
+            self.idx,
+            name,
+        );
+        self.idx += 1;
+        Some((name, value))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+impl<'a, T: Fields, const WORDS: usize> ExactSizeIterator for TrackedIterMut<'a, T, WORDS> {}
+impl<'a, T: Fields, const WORDS: usize> FusedIterator for TrackedIterMut<'a, T, WORDS> {}
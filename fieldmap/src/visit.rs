@@ -0,0 +1,54 @@
+use crate::{FieldsAs, Path, Segment};
+
+/// Callbacks for [`Visit::visit`] to traverse nested [`Fields`](crate::Fields) values.
+///
+/// `I` is the item type of the fields, usually a trait object such as `dyn Debug`.
+pub trait Visitor<I: ?Sized> {
+    /// Called before the fields of a struct or an enum.
+    ///
+    /// `path` is the path of the value, or `None` for the value on which [`Visit::visit`] was called.
+    fn enter_struct(&mut self, path: Option<&Path>) {
+        let _ = path;
+    }
+    /// Called for a field that is not `#[fields(nested)]`.
+    fn leaf(&mut self, path: &Path, value: &I);
+    /// Called after the fields of a struct or an enum.
+    fn leave_struct(&mut self, path: Option<&Path>) {
+        let _ = path;
+    }
+}
+
+/// A value whose fields can be traversed recursively by a [`Visitor`].
+///
+/// `#[derive(Fields)]` implements `Visit<dyn Trait>` for each `#[fields(item = Trait)]`.
+/// Fields with `#[fields(nested)]` are traversed recursively, and the other fields are passed to [`Visitor::leaf`].
+pub trait Visit<I: ?Sized> {
+    /// Traverses the fields of `self`.
+    fn visit(&self, visitor: &mut dyn Visitor<I>) {
+        self.visit_with(None, visitor);
+    }
+    /// Traverses the fields of `self` located at `path`.
+    fn visit_with(&self, path: Option<&Path>, visitor: &mut dyn Visitor<I>);
+
+    /// Traverses the fields of `self` located at `path` as if they were named `names`,
+    /// without calling [`Visitor::enter_struct`] and [`Visitor::leave_struct`] for `self`.
+    ///
+    /// Used by `#[fields(flatten)]` to visit the spliced fields with their prefixed names.
+    /// The default implementation passes every field to [`Visitor::leaf`].
+    #[doc(hidden)]
+    fn visit_fields(
+        &self,
+        path: Option<&Path>,
+        names: &[&'static str],
+        visitor: &mut dyn Visitor<I>,
+    ) where
+        Self: FieldsAs<I>,
+    {
+        for (idx, name) in names.iter().enumerate() {
+            let path = Path::new(path, Segment::Name(name));
+            if let Some(value) = self.get_as(idx) {
+                visitor.leaf(&path, value);
+            }
+        }
+    }
+}
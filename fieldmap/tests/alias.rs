@@ -0,0 +1,26 @@
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug, rename_all = "kebab-case")]
+struct ExampleType {
+    #[fields(rename = "port", alias = "listen_port")]
+    #[fields(alias = "listen-port")]
+    port_number: u16,
+    value_u8: u8,
+}
+
+#[test]
+fn test_alias() {
+    assert_eq!(ExampleType::find("port"), Some(0));
+    assert_eq!(ExampleType::find("listen_port"), Some(0));
+    assert_eq!(ExampleType::find("listen-port"), Some(0));
+    assert_eq!(ExampleType::find("port_number"), None);
+    assert_eq!(ExampleType::find("value-u8"), Some(1));
+    assert_eq!(ExampleType::name(0), Some("port"));
+    assert_eq!(ExampleType::len(), 2);
+    assert_eq!(
+        ExampleType::names().collect::<Vec<_>>(),
+        ["port", "value-u8"]
+    );
+}
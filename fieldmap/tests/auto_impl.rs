@@ -15,6 +15,13 @@ struct ExampleTypeIdent {
     value_u16: u16,
 }
 
+#[derive(Fields)]
+#[fields(item = std::fmt::Display)]
+struct BorrowType<'a> {
+    value_s: &'a str,
+    value_u8: u8,
+}
+
 #[test]
 fn test_get_by_idx() {
     use fieldmap::*;
@@ -58,6 +65,27 @@ fn test_iter_mut() {
     assert!(iter.next().is_none());
 }
 
+#[test]
+fn test_borrow() {
+    use fieldmap::*;
+
+    let s = String::from("abc");
+    let mut value = BorrowType {
+        value_s: &s,
+        value_u8: 10,
+    };
+
+    let mut iter = value.iter();
+    assert_next(&mut iter, "value_s", "abc");
+    assert_next(&mut iter, "value_u8", "10");
+    assert!(iter.next().is_none());
+
+    let mut iter = (&mut value).into_iter();
+    assert_next(&mut iter, "value_s", "abc");
+    assert_next(&mut iter, "value_u8", "10");
+    assert!(iter.next().is_none());
+}
+
 #[test]
 fn test_get_static() {
     use fieldmap::*;
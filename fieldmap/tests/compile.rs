@@ -11,3 +11,17 @@ struct GenericType<T: Debug + 'static> {
     v1: (T, T),
     v2: u16,
 }
+
+#[derive(Field, Fields)]
+#[fields(item = "Debug")]
+struct GenericTypeNoStatic<T: Debug> {
+    v1: (T, T),
+    v2: u16,
+}
+
+#[derive(Field, Fields)]
+#[fields(item = "Debug")]
+struct LifetimeType<'a, 'b, T: Debug> {
+    v1: &'a str,
+    v2: &'b mut T,
+}
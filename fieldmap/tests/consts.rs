@@ -0,0 +1,46 @@
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType {
+    value_u8: u8,
+    #[fields(skip)]
+    _cache: u32,
+    #[fields(rename = "u16")]
+    value_u16: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct GenericType<T: Debug> {
+    value_t: T,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+enum ExampleEnum {
+    A(u8),
+}
+
+const NAMES: &[&str] = ExampleType::FIELD_NAMES;
+const LEN: usize = <ExampleType as Fields>::LEN;
+
+#[test]
+fn test_consts() {
+    assert_eq!(NAMES, ["value_u8", "u16"]);
+    assert_eq!(LEN, 2);
+    let stats = [0u32; <ExampleType as Fields>::LEN];
+    assert_eq!(stats.len(), ExampleType::len());
+    assert_eq!(ExampleType::name(1), Some("u16"));
+    assert_eq!(ExampleType::name(2), None);
+    assert_eq!(GenericType::<u8>::FIELD_NAMES, ["value_t"]);
+    assert_eq!(GenericType::<u8>::LEN, 1);
+}
+
+#[test]
+fn test_consts_enum() {
+    assert!(ExampleEnum::FIELD_NAMES.is_empty());
+    assert_eq!(ExampleEnum::LEN, 0);
+    assert_eq!(ExampleEnum::A(1).field_len(), 1);
+}
@@ -0,0 +1,123 @@
+use fieldmap::{diff, DynEq, Fields};
+use std::fmt::Debug;
+
+trait Item: DynEq + Debug {}
+impl<T: DynEq + Debug> Item for T {}
+
+#[derive(Fields, Clone)]
+#[fields(item = Item)]
+struct ExampleType {
+    value_u8: u8,
+    value_s: String,
+    #[fields(skip)]
+    skipped: u32,
+}
+
+#[derive(Fields)]
+#[fields(item = DynEq)]
+enum ExampleEnum {
+    A { x: u8, y: u8 },
+    B(u8),
+}
+
+#[test]
+fn test_dyn_eq() {
+    let a: &dyn DynEq = &1u8;
+    assert!(a.dyn_eq(&1u8));
+    assert!(!a.dyn_eq(&2u8));
+    assert!(!a.dyn_eq(&1u16));
+}
+
+#[test]
+fn test_diff_equal() {
+    let a = ExampleType {
+        value_u8: 1,
+        value_s: "a".into(),
+        skipped: 0,
+    };
+    let b = ExampleType {
+        value_u8: 1,
+        value_s: "a".into(),
+        skipped: 10,
+    };
+    assert_ne!(a.skipped, b.skipped);
+    assert_eq!(diff(&a, &b).count(), 0);
+}
+
+#[test]
+fn test_diff_changed() {
+    let a = ExampleType {
+        value_u8: 1,
+        value_s: "a".into(),
+        skipped: 0,
+    };
+    let b = ExampleType {
+        value_u8: 1,
+        value_s: "b".into(),
+        skipped: 0,
+    };
+    let changes: Vec<_> = diff(&a, &b).collect();
+    assert_eq!(changes.len(), 1);
+    let c = changes[0];
+    assert_eq!(c.name, "value_s");
+    assert_eq!(c.index, 1);
+    assert_eq!(
+        format!("{:?} -> {:?}", c.old.unwrap(), c.new.unwrap()),
+        r#""a" -> "b""#
+    );
+}
+
+#[test]
+fn test_diff_all_changed() {
+    let a = ExampleType {
+        value_u8: 1,
+        value_s: "a".into(),
+        skipped: 0,
+    };
+    let b = ExampleType {
+        value_u8: 2,
+        value_s: "b".into(),
+        skipped: 0,
+    };
+    let names: Vec<_> = diff(&a, &b).map(|c| c.name).collect();
+    assert_eq!(names, ["value_u8", "value_s"]);
+}
+
+#[test]
+fn test_diff_enum() {
+    let a = ExampleEnum::A { x: 1, y: 2 };
+    let b = ExampleEnum::A { x: 1, y: 3 };
+    let changes: Vec<_> = diff(&a, &b).collect();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].name, "y");
+    assert_eq!(
+        changes[0].old.unwrap().as_any().downcast_ref::<u8>(),
+        Some(&2)
+    );
+    assert_eq!(
+        changes[0].new.unwrap().as_any().downcast_ref::<u8>(),
+        Some(&3)
+    );
+}
+
+#[test]
+fn test_diff_enum_variant_changed() {
+    let a = ExampleEnum::A { x: 1, y: 2 };
+    let b = ExampleEnum::B(1);
+    let changes: Vec<_> = diff(&a, &b)
+        .map(|c| {
+            let value =
+                |v: Option<&dyn DynEq>| v.map(|v| *v.as_any().downcast_ref::<u8>().unwrap());
+            (c.name, c.index, value(c.old), value(c.new))
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("x", 0, Some(1), None),
+            ("y", 1, Some(2), None),
+            ("0", 0, None, Some(1)),
+        ]
+    );
+    assert_eq!(diff(&b, &b).count(), 0);
+}
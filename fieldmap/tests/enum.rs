@@ -0,0 +1,72 @@
+use fieldmap::{Fields, FieldsAs};
+use std::fmt::Debug;
+
+#[derive(Fields, Debug, PartialEq)]
+#[fields(item = Debug)]
+enum Shape {
+    Circle {
+        r: f64,
+    },
+    Rect {
+        w: f64,
+        #[fields(rename = "height")]
+        h: f64,
+    },
+    Tuple(u8, #[fields(skip)] (), u16),
+    Unit,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+enum Empty {}
+
+#[test]
+fn test_variant() {
+    let x = Shape::Rect { w: 1.0, h: 2.0 };
+    assert_eq!(x.variant_name(), Some("Rect"));
+    assert_eq!(x.field_len(), 2);
+    assert_eq!(x.field_name(0), Some("w"));
+    assert_eq!(x.field_name(1), Some("height"));
+    assert_eq!(x.field_name(2), None);
+    assert_eq!(x.field_find("height"), Some(1));
+    assert_eq!(x.field_find("r"), None);
+    assert_eq!(format!("{:?}", x.get(1).unwrap()), "2.0");
+    assert!(x.get(2).is_none());
+
+    let x = Shape::Circle { r: 3.0 };
+    assert_eq!(x.variant_name(), Some("Circle"));
+    assert_eq!(x.field_find("r"), Some(0));
+    assert_eq!(x.field_len(), 1);
+
+    let x = Shape::Unit;
+    assert_eq!(x.variant_name(), Some("Unit"));
+    assert_eq!(x.field_len(), 0);
+    assert!(x.get(0).is_none());
+}
+
+#[test]
+fn test_static() {
+    assert_eq!(Shape::len(), 0);
+    assert_eq!(Shape::name(0), None);
+    assert_eq!(Shape::find("r"), None);
+    assert_eq!(Empty::len(), 0);
+}
+
+#[test]
+fn test_iter() {
+    let mut x = Shape::Tuple(1, (), 2);
+    let items: Vec<_> = x
+        .iter()
+        .map(|(name, value)| format!("{} = {:?}", name, value))
+        .collect();
+    assert_eq!(items, ["0 = 1", "2 = 2"]);
+    assert_eq!(x.iter().len(), 2);
+
+    for (_, value) in FieldsAs::<dyn Debug>::iter_mut_as(&mut x) {
+        let _ = format!("{:?}", value);
+    }
+    if let Some(v) = x.get_mut(0) {
+        let _ = format!("{:?}", v);
+    }
+    assert_eq!(x, Shape::Tuple(1, (), 2));
+}
@@ -0,0 +1,46 @@
+use fieldmap::{name_key, FieldByNameExt};
+
+#[derive(fieldmap::Field)]
+struct ExampleType {
+    value_u8: u8,
+    value_u16: u16,
+    x: u32,
+    y: u32,
+    r#type: String,
+}
+
+#[derive(fieldmap::Field)]
+struct TupleType(u8, u8);
+
+const VALUE_U8: u64 = name_key("value_u8");
+
+#[test]
+fn test_field_by_name() {
+    let mut x = ExampleType {
+        value_u8: 1,
+        value_u16: 2,
+        x: 3,
+        y: 4,
+        r#type: "5".into(),
+    };
+    assert_eq!(*x.field::<VALUE_U8>(), 1);
+    assert_eq!(*x.field::<{ name_key("value_u16") }>(), 2);
+    assert_eq!(*x.field::<{ name_key("y") }>(), 4);
+    assert_eq!(x.field::<{ name_key("type") }>(), "5");
+
+    *x.field_mut::<{ name_key("x") }>() = 10;
+    assert_eq!(x.x, 10);
+}
+
+#[test]
+fn test_field_by_name_tuple() {
+    let x = TupleType(1, 2);
+    assert_eq!(*x.field::<{ name_key("1") }>(), 2);
+}
+
+#[test]
+fn test_name_key() {
+    assert_eq!(name_key(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(name_key("a"), 0xaf63_dc4c_8601_ec8c);
+    assert_ne!(name_key("value_u8"), name_key("value_u16"));
+}
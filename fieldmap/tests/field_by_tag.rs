@@ -0,0 +1,62 @@
+use fieldmap::{Field, FieldByTag};
+
+#[derive(Field)]
+struct ExampleType {
+    x: u32,
+    y: u32,
+    value_u8: u8,
+    r#type: u16,
+}
+
+#[derive(Field)]
+struct TupleType(u32, u32);
+
+#[derive(Field)]
+struct GenericType<T> {
+    v1: T,
+    v2: T,
+}
+
+mod inner {
+    #[derive(fieldmap::Field)]
+    pub struct PubType {
+        pub value: u8,
+    }
+}
+
+#[test]
+fn test_get_by_tag() {
+    let mut x = ExampleType {
+        x: 1,
+        y: 2,
+        value_u8: 3,
+        r#type: 4,
+    };
+    assert_eq!(*FieldByTag::<ExampleType_fields::x>::get(&x), 1);
+    assert_eq!(*FieldByTag::<ExampleType_fields::y>::get(&x), 2);
+    assert_eq!(*FieldByTag::<ExampleType_fields::r#type>::get(&x), 4);
+    *FieldByTag::<ExampleType_fields::y>::get_mut(&mut x) = 5;
+    assert_eq!(x.y, 5);
+
+    assert_eq!(*Field::<u8>::get(&x), 3);
+    assert_eq!(*Field::<u16>::get(&x), 4);
+}
+
+#[test]
+fn test_get_by_tag_tuple() {
+    let x = TupleType(1, 2);
+    assert_eq!(*FieldByTag::<TupleType_fields::_0>::get(&x), 1);
+    assert_eq!(*FieldByTag::<TupleType_fields::_1>::get(&x), 2);
+}
+
+#[test]
+fn test_get_by_tag_generic() {
+    let x = GenericType { v1: "a", v2: "b" };
+    assert_eq!(*FieldByTag::<GenericType_fields::v2>::get(&x), "b");
+}
+
+#[test]
+fn test_get_by_tag_pub() {
+    let x = inner::PubType { value: 1 };
+    assert_eq!(*FieldByTag::<inner::PubType_fields::value>::get(&x), 1);
+}
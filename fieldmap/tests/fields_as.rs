@@ -0,0 +1,65 @@
+use fieldmap::{Fields, FieldsAs};
+use std::any::Any;
+use std::fmt::{Debug, Display};
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(item = "Any")]
+struct ExampleType {
+    value_u8: u8,
+    value_s: String,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(item = Display)]
+struct BorrowType<'a> {
+    value_s: &'a str,
+    value_u8: u8,
+}
+
+#[test]
+fn test_get_as() {
+    let value = ExampleType {
+        value_u8: 10,
+        value_s: "abc".into(),
+    };
+    let a: &dyn Any = value.get_as(0).unwrap();
+    assert_eq!(a.downcast_ref::<u8>(), Some(&10));
+    let a: &dyn Any = value.get_as(1).unwrap();
+    assert_eq!(a.downcast_ref::<String>().unwrap(), "abc");
+    assert!(FieldsAs::<dyn Any>::get_as(&value, 2).is_none());
+
+    let d: &dyn Debug = value.get_as(1).unwrap();
+    assert_eq!(format!("{:?}", d), "\"abc\"");
+}
+
+#[test]
+fn test_get_mut_as() {
+    let mut value = ExampleType {
+        value_u8: 10,
+        value_s: "abc".into(),
+    };
+    let a: &mut dyn Any = value.get_mut_as(0).unwrap();
+    *a.downcast_mut::<u8>().unwrap() = 20;
+    assert_eq!(value.value_u8, 20);
+}
+
+#[test]
+fn test_iter_as() {
+    let s = String::from("abc");
+    let mut value = BorrowType {
+        value_s: &s,
+        value_u8: 10,
+    };
+
+    let items: Vec<_> = FieldsAs::<dyn Display>::iter_as(&value)
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    assert_eq!(items, ["value_s = abc", "value_u8 = 10"]);
+
+    let items: Vec<_> = FieldsAs::<dyn Debug>::iter_mut_as(&mut value)
+        .map(|(name, value)| format!("{} = {:?}", name, value))
+        .collect();
+    assert_eq!(items, ["value_s = \"abc\"", "value_u8 = 10"]);
+}
@@ -0,0 +1,64 @@
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType {
+    value_u8: u8,
+    #[fields(alias = "port")]
+    #[fields(alias = "Http-Port")]
+    http_port: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+enum ExampleEnum {
+    A { value_u8: u8 },
+    B(u16),
+}
+
+struct HandImpl;
+
+impl Fields for HandImpl {
+    type Item<'a> = dyn Debug;
+
+    const FIELD_NAMES: &'static [&'static str] = &["value_u8"];
+
+    fn find(name: &str) -> Option<usize> {
+        match name {
+            "value_u8" => Some(0),
+            _ => None,
+        }
+    }
+    fn get(&self, _idx: usize) -> Option<&Self::Item<'_>> {
+        None
+    }
+    fn get_mut(&mut self, _idx: usize) -> Option<&mut Self::Item<'_>> {
+        None
+    }
+}
+
+#[test]
+fn test_find_loose() {
+    for name in ["value_u8", "VALUE_U8", "value-u8", "valueU8", "valueu8"] {
+        assert_eq!(ExampleType::find_loose(name), Some(0), "{}", name);
+        assert_eq!(HandImpl::find_loose(name), Some(0), "{}", name);
+    }
+    assert_eq!(ExampleType::find_loose("HTTP_PORT"), Some(1));
+    assert_eq!(ExampleType::find_loose("Port"), Some(1));
+    assert_eq!(ExampleType::find("Http-Port"), Some(1));
+    assert_eq!(ExampleType::find_loose("value_u16"), None);
+    assert_eq!(ExampleType::find_loose("value_u8_value_u8"), None);
+    assert_eq!(ExampleType::find("VALUE_U8"), None);
+}
+
+#[test]
+fn test_find_loose_enum() {
+    assert_eq!(ExampleEnum::find_loose("value_u8"), None);
+    assert_eq!(
+        ExampleEnum::A { value_u8: 1 }.field_find_loose("VALUE-U8"),
+        Some(0)
+    );
+    assert_eq!(ExampleEnum::B(1).field_find_loose("VALUE-U8"), None);
+    assert_eq!(ExampleEnum::B(1).field_find_loose("0"), Some(0));
+}
@@ -0,0 +1,118 @@
+use fieldmap::{Fields, FieldsAs};
+use std::any::Any;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(item = Any)]
+struct DbConfig {
+    host: String,
+    #[fields(alias = "db_port")]
+    port: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(item = Any)]
+struct HttpConfig {
+    /// Listen port.
+    port: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(item = Any)]
+struct App {
+    name: String,
+    #[fields(flatten, prefix = "db.")]
+    db: DbConfig,
+    #[fields(flatten)]
+    http: HttpConfig,
+    #[fields(rename = "debug_mode")]
+    debug: bool,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Generic<T: Debug + 'static> {
+    #[fields(flatten)]
+    http: HttpConfig,
+    value_t: T,
+}
+
+#[test]
+fn test_flatten_names() {
+    assert_eq!(
+        App::FIELD_NAMES,
+        ["name", "db.host", "db.port", "port", "debug_mode"]
+    );
+    assert_eq!(App::LEN, 5);
+    assert_eq!(App::len(), 5);
+    assert_eq!(App::name(2), Some("db.port"));
+    assert_eq!(App::name(5), None);
+    assert_eq!(
+        Generic::<u8>::names().collect::<Vec<_>>(),
+        ["port", "value_t"]
+    );
+}
+
+#[test]
+fn test_flatten_find() {
+    assert_eq!(App::find("name"), Some(0));
+    assert_eq!(App::find("db.host"), Some(1));
+    assert_eq!(App::find("db.port"), Some(2));
+    assert_eq!(App::find("db.db_port"), Some(2));
+    assert_eq!(App::find("port"), Some(3));
+    assert_eq!(App::find("debug_mode"), Some(4));
+    assert_eq!(App::find("host"), None);
+    assert_eq!(App::find("db.name"), None);
+    assert_eq!(App::find_loose("DB.HOST"), Some(1));
+    assert_eq!(App::find_loose("Debug-Mode"), Some(4));
+    assert_eq!(App::find_loose("PORT"), Some(3));
+    assert_eq!(Generic::<u8>::find("value_t"), Some(1));
+}
+
+#[test]
+fn test_flatten_get() {
+    let mut x = App {
+        name: "app".into(),
+        db: DbConfig {
+            host: "localhost".into(),
+            port: 5432,
+        },
+        http: HttpConfig { port: 80 },
+        debug: true,
+    };
+    let values: Vec<_> = x.iter().map(|(n, v)| format!("{} = {:?}", n, v)).collect();
+    assert_eq!(
+        values,
+        [
+            "name = \"app\"",
+            "db.host = \"localhost\"",
+            "db.port = 5432",
+            "port = 80",
+            "debug_mode = true",
+        ]
+    );
+    assert!(x.get(5).is_none());
+
+    let a: &mut dyn Any = x.get_mut_as(3).unwrap();
+    *a.downcast_mut::<u16>().unwrap() = 8080;
+    assert_eq!(x.http.port, 8080);
+
+    let g = Generic {
+        http: HttpConfig { port: 1 },
+        value_t: 2u8,
+    };
+    assert_eq!(format!("{:?}", g.get(1).unwrap()), "2");
+}
+
+#[test]
+fn test_flatten_info() {
+    assert_eq!(App::info(0).unwrap().name, "name");
+    assert_eq!(App::info(1).unwrap().name, "host");
+    assert_eq!(App::name(1), Some("db.host"));
+    assert_eq!(App::info(3).unwrap().doc, "Listen port.");
+    assert_eq!(App::info(4).unwrap().ty, "bool");
+    assert_eq!(App::info(5), None);
+}
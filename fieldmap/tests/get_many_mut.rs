@@ -0,0 +1,180 @@
+use fieldmap::{DisjointError, Fields};
+use std::fmt::Debug;
+
+#[derive(Fields, Debug, PartialEq)]
+#[fields(item = Debug)]
+struct ExampleType {
+    a: u8,
+    #[fields(skip)]
+    skipped: u8,
+    #[fields(alias = "bb")]
+    b: String,
+    c: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Tuple(u8, u16);
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Unit;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Generic<N: Debug> {
+    value: N,
+    other: N,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+enum ExampleEnum {
+    A { x: u8, y: u8 },
+    B(u16),
+    C,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Flatten {
+    #[fields(flatten)]
+    inner: Inner,
+    d: u8,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Inner {
+    x: u8,
+    y: u8,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Deep {
+    a: u8,
+    #[fields(flatten, prefix = "f.")]
+    f: Flatten,
+}
+
+fn debug<const N: usize>(items: [&mut dyn Debug; N]) -> Vec<String> {
+    items.iter().map(|v| format!("{v:?}")).collect()
+}
+
+#[test]
+fn test_get_many_mut() {
+    let mut x = ExampleType {
+        a: 1,
+        skipped: 0,
+        b: "b".into(),
+        c: 3,
+    };
+    let [c, a] = x.get_many_mut([2, 0]).unwrap();
+    assert_eq!(format!("{c:?} {a:?}"), "3 1");
+    assert_eq!(
+        debug(x.get_many_mut([0, 1, 2]).unwrap()),
+        ["1", "\"b\"", "3"]
+    );
+    assert_eq!(x.get_many_mut([]).unwrap().len(), 0);
+    assert_eq!(x.skipped, 0);
+}
+
+#[test]
+fn test_get_many_mut_error() {
+    let mut x = ExampleType {
+        a: 1,
+        skipped: 0,
+        b: "b".into(),
+        c: 3,
+    };
+    assert_eq!(
+        x.get_many_mut([0, 3]).unwrap_err(),
+        DisjointError::NotFound { position: 1 }
+    );
+    assert_eq!(
+        x.get_many_mut([1, 2, 1]).unwrap_err(),
+        DisjointError::Overlapping {
+            position: 2,
+            first: 0
+        }
+    );
+    assert_eq!(
+        DisjointError::Overlapping {
+            position: 2,
+            first: 0
+        }
+        .to_string(),
+        "field at position 2 is the same as the field at position 0"
+    );
+}
+
+#[test]
+fn test_get_many_mut_by_name() {
+    let mut x = ExampleType {
+        a: 1,
+        skipped: 0,
+        b: "b".into(),
+        c: 3,
+    };
+    let [b, a] = x.get_many_mut_by_name(["bb", "a"]).unwrap();
+    assert_eq!(format!("{b:?} {a:?}"), "\"b\" 1");
+    assert_eq!(
+        x.get_many_mut_by_name(["a", "skipped"]).unwrap_err(),
+        DisjointError::NotFound { position: 1 }
+    );
+    assert_eq!(
+        x.get_many_mut_by_name(["b", "bb"]).unwrap_err(),
+        DisjointError::Overlapping {
+            position: 1,
+            first: 0
+        }
+    );
+}
+
+#[test]
+fn test_tuple_unit_generic() {
+    let mut x = Tuple(1, 2);
+    assert_eq!(debug(x.get_many_mut([1, 0]).unwrap()), ["2", "1"]);
+    assert_eq!(x.0, 1);
+    assert!(Unit.get_many_mut([0]).is_err());
+    assert_eq!(Unit.get_many_mut([]).unwrap().len(), 0);
+    let mut x = Generic { value: 1, other: 2 };
+    assert_eq!(debug(x.get_many_mut([1, 0]).unwrap()), ["2", "1"]);
+}
+
+#[test]
+fn test_enum_value() {
+    let mut x = ExampleEnum::A { x: 1, y: 2 };
+    assert_eq!(debug(x.get_many_mut([1, 0]).unwrap()), ["2", "1"]);
+    assert!(x.get_many_mut([2]).is_err());
+    let mut x = ExampleEnum::B(3);
+    assert_eq!(debug(x.get_many_mut([0]).unwrap()), ["3"]);
+    assert!(ExampleEnum::C.get_many_mut([0]).is_err());
+}
+
+#[test]
+fn test_flatten() {
+    let mut x = Flatten {
+        inner: Inner { x: 1, y: 2 },
+        d: 3,
+    };
+    assert_eq!(debug(x.get_many_mut([2, 0, 1]).unwrap()), ["3", "1", "2"]);
+    assert!(x.get_many_mut([3]).is_err());
+
+    let mut x = Deep {
+        a: 0,
+        f: Flatten {
+            inner: Inner { x: 1, y: 2 },
+            d: 3,
+        },
+    };
+    let [y, a, d] = x.get_many_mut([2, 0, 3]).unwrap();
+    assert_eq!(format!("{y:?} {a:?} {d:?}"), "2 0 3");
+    assert_eq!(
+        x.get_many_mut_by_name(["f.d", "f.x"])
+            .map(|[d, x]| format!("{d:?} {x:?}"))
+            .unwrap(),
+        "3 1"
+    );
+}
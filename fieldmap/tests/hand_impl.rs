@@ -9,33 +9,44 @@ struct ExampleType {
 // Begin hand impl
 
 impl ::fieldmap::Fields for ExampleType {
-    type Item = dyn std::fmt::Display;
+    type Item<'a>
+        = dyn std::fmt::Display + 'a
+    where
+        Self: 'a;
+
+    const FIELD_NAMES: &'static [&'static str] = &["value_u8", "value_u16"];
 
     #[inline]
-    fn len() -> usize {
-        2
+    fn find(name: &str) -> ::core::option::Option<usize> {
+        match name {
+            "value_u8" => Some(0),
+            "value_u16" => Some(1),
+            _ => None,
+        }
     }
 
     #[inline]
-    fn name(idx: usize) -> ::core::option::Option<&'static str> {
+    fn get(&self, idx: usize) -> ::core::option::Option<&Self::Item<'_>> {
         match idx {
-            0 => Some("value_u8"),
-            1 => Some("value_u16"),
+            0 => Some(&self.value_u8),
+            1 => Some(&self.value_u16),
             _ => None,
         }
     }
 
     #[inline]
-    fn find(name: &str) -> ::core::option::Option<usize> {
-        match name {
-            "value_u8" => Some(0),
-            "value_u16" => Some(1),
+    fn get_mut(&mut self, idx: usize) -> ::core::option::Option<&mut Self::Item<'_>> {
+        match idx {
+            0 => Some(&mut self.value_u8),
+            1 => Some(&mut self.value_u16),
             _ => None,
         }
     }
+}
 
+impl<'_a> ::fieldmap::FieldsAs<dyn std::fmt::Display + '_a> for ExampleType {
     #[inline]
-    fn get(&self, idx: usize) -> ::core::option::Option<&Self::Item> {
+    fn get_as(&self, idx: usize) -> ::core::option::Option<&(dyn std::fmt::Display + '_a)> {
         match idx {
             0 => Some(&self.value_u8),
             1 => Some(&self.value_u16),
@@ -44,7 +55,10 @@ impl ::fieldmap::Fields for ExampleType {
     }
 
     #[inline]
-    fn get_mut(&mut self, idx: usize) -> ::core::option::Option<&mut Self::Item> {
+    fn get_mut_as(
+        &mut self,
+        idx: usize,
+    ) -> ::core::option::Option<&mut (dyn std::fmt::Display + '_a)> {
         match idx {
             0 => Some(&mut self.value_u8),
             1 => Some(&mut self.value_u16),
@@ -53,6 +67,23 @@ impl ::fieldmap::Fields for ExampleType {
     }
 }
 
+impl<'_a> ::fieldmap::Visit<dyn std::fmt::Display + '_a> for ExampleType {
+    fn visit_with(
+        &self,
+        path: ::core::option::Option<&::fieldmap::Path>,
+        visitor: &mut dyn ::fieldmap::Visitor<dyn std::fmt::Display + '_a>,
+    ) {
+        visitor.enter_struct(path);
+        ::fieldmap::Visit::visit_fields(
+            self,
+            path,
+            <Self as ::fieldmap::Fields>::FIELD_NAMES,
+            visitor,
+        );
+        visitor.leave_struct(path);
+    }
+}
+
 impl<'_a> ::core::iter::IntoIterator for &'_a ExampleType {
     type Item = <::fieldmap::Iter<'_a, ExampleType> as Iterator>::Item;
     type IntoIter = ::fieldmap::Iter<'_a, ExampleType>;
@@ -82,7 +113,7 @@ impl ::fieldmap::Field<u8> for ExampleType {
     }
 }
 
-impl<'a> ::fieldmap::Field<u16> for ExampleType {
+impl ::fieldmap::Field<u16> for ExampleType {
     #[inline]
     fn get(&self) -> &u16 {
         &self.value_u16
@@ -140,6 +171,61 @@ fn test_iter_mut() {
     assert!(iter.next().is_none());
 }
 
+#[test]
+fn test_get_many_mut() {
+    use fieldmap::*;
+
+    let mut value = ExampleType {
+        value_u8: 10,
+        value_u16: 15,
+    };
+    let [v] = value.get_many_mut([1]).unwrap();
+    assert_eq!(v.to_string(), "15");
+    assert_eq!(value.get_many_mut([]).unwrap().len(), 0);
+    assert_eq!(
+        value.get_many_mut([0, 1]).err(),
+        Some(DisjointError::Unsupported { position: 1 })
+    );
+    assert_eq!(
+        value.get_many_mut([0, 0]).err(),
+        Some(DisjointError::Overlapping {
+            position: 1,
+            first: 0
+        })
+    );
+}
+
+#[test]
+fn test_get_many_mut_flatten() {
+    use fieldmap::*;
+
+    #[derive(Fields)]
+    #[fields(item = Display)]
+    struct Outer {
+        a: u8,
+        #[fields(flatten)]
+        inner: ExampleType,
+    }
+
+    let mut value = Outer {
+        a: 5,
+        inner: ExampleType {
+            value_u8: 10,
+            value_u16: 15,
+        },
+    };
+    let [a, v] = value.get_many_mut([0, 2]).unwrap();
+    assert_eq!(format!("{a} {v}"), "5 15");
+    assert_eq!(
+        value.get_many_mut([0, 1, 2]).err(),
+        Some(DisjointError::Unsupported { position: 2 })
+    );
+    assert_eq!(
+        DisjointError::Unsupported { position: 2 }.to_string(),
+        "field at position 2 cannot be borrowed together with the fields before it"
+    );
+}
+
 #[test]
 fn test_get_static() {
     use fieldmap::*;
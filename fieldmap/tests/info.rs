@@ -0,0 +1,68 @@
+use fieldmap::{FieldInfo, Fields};
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+pub struct ExampleType<T: Debug> {
+    /// The first value.
+    ///
+    /// Second paragraph.
+    pub value_u8: u8,
+    #[fields(meta(label = "Value", unit = "ms"))]
+    #[fields(meta(r#type = "number"))]
+    pub(crate) value_vec: Vec<T>,
+    value_t: (String, &'static [u8; 4], &'static mut (u8,)),
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+enum ExampleEnum {
+    A(u8),
+    B {
+        /// x
+        #[fields(meta(label = "X"))]
+        x: u16,
+    },
+}
+
+#[test]
+fn test_info() {
+    assert_eq!(
+        ExampleType::<u8>::info(0),
+        Some(&FieldInfo {
+            name: "value_u8",
+            ty: "u8",
+            doc: "The first value.\n\nSecond paragraph.",
+            vis: "pub",
+            meta: &[],
+        })
+    );
+    let info = ExampleType::<u8>::info(1).unwrap();
+    assert_eq!(info.name, "value_vec");
+    assert_eq!(info.ty, "Vec<T>");
+    assert_eq!(info.doc, "");
+    assert_eq!(info.vis, "pub(crate)");
+    assert_eq!(
+        info.meta,
+        [("label", "Value"), ("unit", "ms"), ("type", "number")]
+    );
+    assert_eq!(info.meta("unit"), Some("ms"));
+    assert_eq!(info.meta("none"), None);
+    let info = ExampleType::<u8>::info(2).unwrap();
+    assert_eq!(info.ty, "(String, &'static [u8; 4], &'static mut (u8,))");
+    assert_eq!(info.vis, "");
+    assert_eq!(ExampleType::<u8>::info(3), None);
+}
+
+#[test]
+fn test_info_enum() {
+    assert_eq!(ExampleEnum::info(0), None);
+    let a = ExampleEnum::A(1);
+    assert_eq!(a.field_info(0).unwrap().ty, "u8");
+    assert_eq!(a.field_info(1), None);
+    let b = ExampleEnum::B { x: 2 };
+    let info = b.field_info(0).unwrap();
+    assert_eq!(info.name, "x");
+    assert_eq!(info.doc, "x");
+    assert_eq!(info.meta("label"), Some("X"));
+}
@@ -0,0 +1,44 @@
+#![no_std]
+
+use fieldmap::{Field, Fields, TryField};
+
+#[derive(Fields, Field)]
+#[fields(item = "core::fmt::Debug")]
+struct ExampleType<'a> {
+    value_u8: u8,
+    value_s: &'a str,
+}
+
+#[derive(Fields, Field)]
+#[fields(item = "core::fmt::Debug")]
+enum ExampleEnum {
+    A(u8),
+    B { x: u16, y: u32 },
+}
+
+#[test]
+fn test_no_std() {
+    let mut value = ExampleType {
+        value_u8: 10,
+        value_s: "abc",
+    };
+    assert_eq!(ExampleType::len(), 2);
+    assert_eq!(ExampleType::find("value_s"), Some(1));
+    assert_eq!(*Field::<u8>::get(&value), 10);
+    *Field::<&str>::get_mut(&mut value) = "def";
+    assert_eq!(value.value_s, "def");
+    assert_eq!((&value).into_iter().count(), 2);
+}
+
+#[test]
+fn test_no_std_enum() {
+    let value = ExampleEnum::A(1);
+    assert_eq!(value.field_len(), 1);
+    assert_eq!(TryField::<u8>::try_get(&value), Some(&1));
+
+    let value = ExampleEnum::B { x: 1, y: 2 };
+    assert_eq!(value.field_len(), 2);
+    assert_eq!(value.field_name(1), Some("y"));
+    assert_eq!(TryField::<u32>::try_get(&value), Some(&2));
+    assert_eq!(TryField::<u8>::try_get(&value), None);
+}
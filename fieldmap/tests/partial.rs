@@ -0,0 +1,160 @@
+use fieldmap::{Fields, MergeItem};
+use std::fmt::Debug;
+
+#[derive(Fields, Debug, Clone, PartialEq)]
+#[fields(item = Debug, partial = "PartialConfig")]
+#[fields(item = MergeItem)]
+#[fields(rename_all = "kebab-case")]
+pub struct Config {
+    /// Host name.
+    pub host: String,
+    #[fields(rename = "PORT", alias = "p")]
+    #[fields(meta(r#type = "number"))]
+    port: u16,
+    #[fields(skip)]
+    skipped: u8,
+    verbose_level: u8,
+}
+
+#[derive(Fields, Debug, Clone, PartialEq)]
+#[fields(item = Debug, partial = "PartialTuple")]
+struct Tuple(u8, #[fields(skip)] u16, String);
+
+#[derive(Fields, Debug, Clone, PartialEq)]
+#[fields(item = Debug, partial = "PartialGeneric")]
+struct Generic<T: Clone + Debug> {
+    value: T,
+}
+
+#[derive(Fields, Clone)]
+#[fields(item = Debug, partial = "PartialUnit")]
+struct Unit;
+
+#[test]
+fn test_names() {
+    assert_eq!(PartialConfig::FIELD_NAMES, Config::FIELD_NAMES);
+    assert_eq!(
+        PartialConfig::FIELD_NAMES,
+        ["host", "PORT", "verbose-level"]
+    );
+    assert_eq!(PartialConfig::find("p"), Config::find("p"));
+    assert_eq!(PartialTuple::FIELD_NAMES, Tuple::FIELD_NAMES);
+    assert_eq!(PartialUnit::LEN, 0);
+}
+
+#[test]
+fn test_info() {
+    let info = PartialConfig::info(0).unwrap();
+    assert_eq!(info.doc, "Host name.");
+    assert_eq!(info.vis, "pub");
+    assert_eq!(PartialConfig::info(1).unwrap().meta("type"), Some("number"));
+}
+
+#[test]
+fn test_default_is_empty() {
+    let p = PartialConfig::default();
+    assert!(p.values().all(|v| format!("{v:?}") == "None"));
+    let mut c = Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 1,
+        verbose_level: 0,
+    };
+    p.apply_to(&mut c);
+    assert_eq!(
+        c,
+        Config {
+            host: "localhost".into(),
+            port: 80,
+            skipped: 1,
+            verbose_level: 0,
+        }
+    );
+}
+
+#[test]
+fn test_to_partial() {
+    let c = Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 1,
+        verbose_level: 0,
+    };
+    let p = c.to_partial();
+    assert_eq!(p.host.as_deref(), Some("localhost"));
+    assert_eq!(p.port, Some(80));
+    assert_eq!(p.verbose_level, Some(0));
+}
+
+#[test]
+fn test_apply_to() {
+    let p = PartialConfig {
+        port: Some(8080),
+        ..Default::default()
+    };
+    let mut c = Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 1,
+        verbose_level: 0,
+    };
+    p.apply_to(&mut c);
+    assert_eq!(c.host, "localhost");
+    assert_eq!(c.port, 8080);
+    assert_eq!(c.skipped, 1);
+}
+
+#[test]
+fn test_layers() {
+    let file = PartialConfig {
+        host: Some("file".into()),
+        port: Some(1),
+        ..Default::default()
+    };
+    let mut cli = PartialConfig::default();
+    cli.merge_from(
+        &PartialConfig {
+            port: Some(2),
+            ..Default::default()
+        },
+        |_, v| v.downcast_ref::<Option<u16>>() != Some(&None),
+    );
+
+    let mut c = Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 1,
+        verbose_level: 0,
+    };
+    for layer in [&file, &cli] {
+        layer.apply_to(&mut c);
+    }
+    assert_eq!(c.host, "file");
+    assert_eq!(c.port, 2);
+}
+
+#[test]
+fn test_tuple() {
+    let t = Tuple(1, 2, "a".into());
+    let p = t.to_partial();
+    assert_eq!(p.0, Some(1));
+    assert_eq!(p.1.as_deref(), Some("a"));
+    let mut t2 = Tuple(0, 5, String::new());
+    PartialTuple(None, Some("b".into())).apply_to(&mut t2);
+    assert_eq!(t2, Tuple(0, 5, "b".into()));
+}
+
+#[test]
+fn test_generic() {
+    let p = Generic { value: 1u8 }.to_partial();
+    assert_eq!(p.value, Some(1));
+    let mut g = Generic { value: 0u8 };
+    p.apply_to(&mut g);
+    assert_eq!(g.value, 1);
+}
+
+#[test]
+fn test_unit() {
+    let _ = Unit.to_partial();
+    PartialUnit.apply_to(&mut Unit);
+}
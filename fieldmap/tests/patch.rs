@@ -0,0 +1,167 @@
+use fieldmap::{Fields, MergeItem, Patch};
+
+#[derive(Fields, Debug, Default, Clone, PartialEq)]
+#[fields(item = MergeItem)]
+struct Config {
+    host: String,
+    port: Option<u16>,
+    verbose: bool,
+}
+
+#[derive(Fields, Clone)]
+#[fields(item = MergeItem)]
+enum ExampleEnum {
+    A { x: u8, y: u8 },
+    B(String),
+}
+
+#[test]
+fn test_assign_from() {
+    let mut x = 1u8;
+    assert!(x.assign_from(&2u8));
+    assert_eq!(x, 2);
+    assert!(!x.assign_from(&3u16));
+    assert_eq!(x, 2);
+}
+
+#[test]
+fn test_merge_from_all() {
+    let mut a = Config::default();
+    let b = Config {
+        host: "localhost".into(),
+        port: Some(80),
+        verbose: true,
+    };
+    a.merge_from(&b, |_, _| true);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_merge_from_predicate() {
+    let mut a = Config {
+        host: "localhost".into(),
+        port: Some(80),
+        verbose: false,
+    };
+    let b = Config {
+        host: "example.com".into(),
+        port: None,
+        verbose: true,
+    };
+    a.merge_from(&b, |_, v| v.downcast_ref::<Option<u16>>() != Some(&None));
+    assert_eq!(
+        a,
+        Config {
+            host: "example.com".into(),
+            port: Some(80),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn test_merge_from_enum() {
+    let mut a = ExampleEnum::A { x: 1, y: 2 };
+    a.merge_from(&ExampleEnum::A { x: 3, y: 4 }, |idx, _| idx == 1);
+    assert!(matches!(a, ExampleEnum::A { x: 1, y: 4 }));
+
+    a.merge_from(&ExampleEnum::B("b".into()), |_, _| true);
+    assert!(matches!(a, ExampleEnum::A { x: 1, y: 4 }));
+}
+
+#[test]
+fn test_patch_empty() {
+    let p = Patch::<Config>::default();
+    assert!(p.is_empty());
+    assert_eq!(p.len(), 0);
+    let mut c = Config {
+        host: "localhost".into(),
+        port: Some(80),
+        verbose: false,
+    };
+    p.apply(&mut c);
+    assert_eq!(
+        c,
+        Config {
+            host: "localhost".into(),
+            port: Some(80),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn test_patch_set() {
+    let mut p = Patch::<Config>::default();
+    assert!(p.set(1, &Some(8080u16)));
+    assert!(!p.set(2, &1u8));
+    assert!(!p.set(3, &true));
+    assert!(p.is_set(1));
+    assert!(!p.is_set(2));
+    assert_eq!(p.indices().collect::<Vec<_>>(), [1]);
+    assert!(p.get(0).is_none());
+    assert!(p.get(1).is_some());
+
+    let mut c = Config {
+        host: "localhost".into(),
+        port: Some(80),
+        verbose: false,
+    };
+    p.apply(&mut c);
+    assert_eq!(c.host, "localhost");
+    assert_eq!(c.port, Some(8080));
+
+    p.unset(1);
+    assert!(p.is_empty());
+}
+
+#[test]
+fn test_patch_get_mut() {
+    let mut p = Patch::<Config>::default();
+    assert!(p.get_mut(3).is_none());
+    p.get_mut(2).unwrap().assign_from(&true);
+    assert_eq!(p.indices().collect::<Vec<_>>(), [2]);
+    assert!(p.value().verbose);
+}
+
+#[test]
+fn test_patch_all() {
+    let p = Patch::all(Config {
+        host: "localhost".into(),
+        port: Some(80),
+        verbose: true,
+    });
+    assert_eq!(p.len(), 3);
+    let mut c = Config::default();
+    p.apply(&mut c);
+    assert_eq!(&c, p.value());
+    assert_eq!(
+        p.into_value(),
+        Config {
+            host: "localhost".into(),
+            port: Some(80),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn test_patch_layers() {
+    let mut file = Patch::<Config>::default();
+    file.set(0, &String::from("file"));
+    file.set(1, &Some(1u16));
+    let mut cli = Patch::<Config>::default();
+    cli.set(1, &Some(2u16));
+
+    let mut c = Config {
+        host: "localhost".into(),
+        port: Some(80),
+        verbose: false,
+    };
+    for layer in [&file, &cli] {
+        layer.apply(&mut c);
+    }
+    assert_eq!(c.host, "file");
+    assert_eq!(c.port, Some(2));
+    assert!(!c.verbose);
+}
@@ -0,0 +1,131 @@
+use fieldmap::{Fields, FieldsPath, PathItem, Segment};
+
+#[derive(Fields)]
+#[fields(item = PathItem)]
+struct Point(u32, u32);
+
+#[derive(Fields)]
+#[fields(item = PathItem)]
+struct Layer {
+    name: String,
+    points: Vec<Point>,
+}
+
+#[derive(Fields)]
+#[fields(item = PathItem)]
+struct Doc {
+    layers: [Layer; 1],
+    scale: f64,
+}
+
+#[derive(Fields)]
+#[fields(item = PathItem)]
+enum Value {
+    Int(i64),
+    Pair { a: Point, b: Point },
+}
+
+#[test]
+fn test_get_path() {
+    let x = Doc {
+        layers: [Layer {
+            name: "base".into(),
+            points: vec![Point(1, 2), Point(3, 4)],
+        }],
+        scale: 1.5,
+    };
+    let v = x.get_path("scale").unwrap();
+    assert_eq!(v.downcast_ref::<f64>(), Some(&1.5));
+    let v = x.get_path("layers.0.name").unwrap();
+    assert_eq!(v.downcast_ref::<String>().unwrap(), "base");
+    let v = x.get_path("layers.0.points.1.0").unwrap();
+    assert_eq!(v.downcast_ref::<u32>(), Some(&3));
+    let v = x.get_path("layers.0.points").unwrap();
+    assert_eq!(v.downcast_ref::<Vec<Point>>().unwrap().len(), 2);
+}
+
+#[test]
+fn test_get_path_error() {
+    let x = Doc {
+        layers: [Layer {
+            name: "base".into(),
+            points: vec![Point(1, 2), Point(3, 4)],
+        }],
+        scale: 1.5,
+    };
+    let e = x.get_path("layers.0.points.2.0").err().unwrap();
+    assert_eq!(e.path(), "layers.0.points.2.0");
+    assert_eq!(e.index(), 3);
+    assert_eq!(e.segment(), "2");
+    assert_eq!(
+        e.to_string(),
+        "`2` (segment 3) of path `layers.0.points.2.0` was not found"
+    );
+    assert_eq!(x.get_path("scale.x").err().unwrap().segment(), "x");
+    assert_eq!(x.get_path("layers.first").err().unwrap().index(), 1);
+    assert_eq!(x.get_path("").err().unwrap().index(), 0);
+}
+
+#[test]
+fn test_get_path_mut() {
+    let mut x = Doc {
+        layers: [Layer {
+            name: "base".into(),
+            points: vec![Point(1, 2), Point(3, 4)],
+        }],
+        scale: 1.5,
+    };
+    *x.get_path_mut("layers.0.points.0.1")
+        .unwrap()
+        .downcast_mut::<u32>()
+        .unwrap() = 20;
+    assert_eq!(x.layers[0].points[0].1, 20);
+    assert!(x.get_path_mut("layers.0.points.0.2").is_err());
+}
+
+#[test]
+fn test_get_path_enum() {
+    let x = Value::Pair {
+        a: Point(1, 2),
+        b: Point(3, 4),
+    };
+    let v = x.get_path("b.1").unwrap();
+    assert_eq!(v.downcast_ref::<u32>(), Some(&4));
+    assert!(Value::Int(1).get_path("b").is_err());
+    let v = Value::Int(1);
+    assert_eq!(v.get_path("0").unwrap().downcast_ref::<i64>(), Some(&1));
+}
+
+#[test]
+fn test_walk() {
+    let x = Doc {
+        layers: [Layer {
+            name: "base".into(),
+            points: vec![Point(1, 2), Point(3, 4)],
+        }],
+        scale: 1.5,
+    };
+    let mut paths = Vec::new();
+    x.walk(&mut |path, value| {
+        if let Some(v) = value.downcast_ref::<u32>() {
+            paths.push(format!("{} = {}", path, v));
+        }
+    });
+    assert_eq!(
+        paths,
+        [
+            "layers.0.points.0.0 = 1",
+            "layers.0.points.0.1 = 2",
+            "layers.0.points.1.0 = 3",
+            "layers.0.points.1.1 = 4",
+        ]
+    );
+
+    let mut segments = Vec::new();
+    x.walk(&mut |path, _| {
+        if path.depth() == 2 {
+            segments.push((path.parent().unwrap().segment(), path.segment()));
+        }
+    });
+    assert_eq!(segments, [(Segment::Name("layers"), Segment::Index(0)),]);
+}
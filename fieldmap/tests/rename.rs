@@ -0,0 +1,54 @@
+use fieldmap::Fields;
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug, rename_all = "kebab-case")]
+struct KebabType {
+    value_u8: u8,
+    #[fields(rename = "u16")]
+    value_u16: u16,
+    r#type: u8,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+#[fields(rename_all = "camelCase")]
+struct CamelType {
+    value_u8: u8,
+    _value_u16: u16,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug, rename_all = "SCREAMING_SNAKE_CASE")]
+struct ScreamingType {
+    value_u8: u8,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug, rename_all = "PascalCase")]
+struct TupleType(u8, #[fields(rename = "second")] u16);
+
+#[test]
+fn test_rename_all() {
+    assert_eq!(
+        KebabType::names().collect::<Vec<_>>(),
+        ["value-u8", "u16", "type"]
+    );
+    assert_eq!(KebabType::find("value-u8"), Some(0));
+    assert_eq!(KebabType::find("value_u8"), None);
+    assert_eq!(KebabType::find("u16"), Some(1));
+    assert_eq!(KebabType::find("value-u16"), None);
+    assert_eq!(KebabType::name(2), Some("type"));
+
+    assert_eq!(
+        CamelType::names().collect::<Vec<_>>(),
+        ["valueU8", "_valueU16"]
+    );
+    assert_eq!(ScreamingType::find("VALUE_U8"), Some(0));
+}
+
+#[test]
+fn test_rename_tuple() {
+    assert_eq!(TupleType::names().collect::<Vec<_>>(), ["0", "second"]);
+    assert_eq!(TupleType::find("second"), Some(1));
+}
@@ -0,0 +1,191 @@
+use fieldmap::serde::{deserialize_fields, erased_serde, AsMap, DeserializeItem};
+use fieldmap::{Fields, MergeItem, Patch};
+
+#[derive(Fields, Debug, PartialEq)]
+#[fields(item = erased_serde::Serialize)]
+#[fields(item = DeserializeItem)]
+struct ExampleType {
+    value_u8: u8,
+    #[fields(alias = "s")]
+    value_s: String,
+    #[fields(skip)]
+    skipped: u32,
+}
+
+#[derive(Fields)]
+#[fields(item = erased_serde::Serialize)]
+enum ExampleEnum {
+    A { x: u8, y: u8 },
+    B(String),
+}
+
+#[test]
+fn test_serialize_struct() {
+    let x = ExampleType {
+        value_u8: 10,
+        value_s: "abc".into(),
+        skipped: 5,
+    };
+    let s = serde_json::to_string(&AsMap(&x)).unwrap();
+    assert_eq!(s, r#"{"value_u8":10,"value_s":"abc"}"#);
+}
+
+#[test]
+fn test_serialize_enum() {
+    let s = serde_json::to_string(&AsMap(&ExampleEnum::A { x: 1, y: 2 })).unwrap();
+    assert_eq!(s, r#"{"x":1,"y":2}"#);
+    let s = serde_json::to_string(&AsMap(&ExampleEnum::B("b".into()))).unwrap();
+    assert_eq!(s, r#"{"0":"b"}"#);
+}
+
+#[test]
+fn test_deserialize() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"value_s":"abc","value_u8":10}"#;
+    deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap();
+    assert_eq!(x.value_u8, 10);
+    assert_eq!(x.value_s, "abc");
+}
+
+#[test]
+fn test_deserialize_alias() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"value_u8":10,"s":"abc"}"#;
+    deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap();
+    assert_eq!(x.value_s, "abc");
+}
+
+#[test]
+fn test_deserialize_round_trip() {
+    let x = ExampleType {
+        value_u8: 10,
+        value_s: "abc".into(),
+        skipped: 0,
+    };
+    let json = serde_json::to_string(&AsMap(&x)).unwrap();
+    let mut y = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    deserialize_fields(&mut y, &mut serde_json::Deserializer::from_str(&json)).unwrap();
+    assert_eq!(y, x);
+}
+
+#[test]
+fn test_deserialize_unknown() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"value_u8":10,"other":1}"#;
+    let e = deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap_err();
+    assert!(e.to_string().starts_with("unknown field `other`"), "{e}");
+}
+
+#[test]
+fn test_deserialize_skipped_is_unknown() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"skipped":1}"#;
+    let e = deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap_err();
+    assert!(e.to_string().starts_with("unknown field `skipped`"), "{e}");
+}
+
+#[test]
+fn test_deserialize_missing() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"value_u8":10}"#;
+    let e = deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap_err();
+    assert!(e.to_string().starts_with("missing field `value_s`"), "{e}");
+
+    let json = r#"{}"#;
+    let e = deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap_err();
+    assert!(
+        e.to_string()
+            .starts_with("missing fields `value_u8`, `value_s`"),
+        "{e}"
+    );
+}
+
+#[test]
+fn test_deserialize_duplicate() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"value_u8":10,"value_u8":11,"value_s":""}"#;
+    let e = deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap_err();
+    assert!(
+        e.to_string().starts_with("duplicate field `value_u8`"),
+        "{e}"
+    );
+}
+
+#[test]
+fn test_deserialize_invalid_value() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        skipped: 0,
+    };
+    let json = r#"{"value_u8":"abc","value_s":""}"#;
+    let e = deserialize_fields(&mut x, &mut serde_json::Deserializer::from_str(json)).unwrap_err();
+    assert!(e.to_string().contains("invalid type"), "{e}");
+}
+
+#[derive(Fields, Default, Debug, PartialEq)]
+#[fields(item = erased_serde::Serialize)]
+#[fields(item = DeserializeItem)]
+#[fields(item = MergeItem)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn test_serialize_patch() {
+    let mut p = Patch::<Config>::default();
+    assert_eq!(serde_json::to_string(&p).unwrap(), "{}");
+    p.set(1, &8080u16);
+    assert_eq!(serde_json::to_string(&p).unwrap(), r#"{"port":8080}"#);
+}
+
+#[test]
+fn test_deserialize_patch() {
+    let p: Patch<Config> = serde_json::from_str(r#"{"port":8080}"#).unwrap();
+    assert_eq!(p.indices().collect::<Vec<_>>(), [1]);
+
+    let mut c = Config {
+        host: "localhost".into(),
+        port: 80,
+    };
+    p.apply(&mut c);
+    assert_eq!(c.host, "localhost");
+    assert_eq!(c.port, 8080);
+}
+
+#[test]
+fn test_deserialize_patch_error() {
+    let e = serde_json::from_str::<Patch<Config>>(r#"{"other":1}"#).unwrap_err();
+    assert!(e.to_string().starts_with("unknown field `other`"), "{e}");
+    let e = serde_json::from_str::<Patch<Config>>(r#"{"port":1,"port":2}"#).unwrap_err();
+    assert!(e.to_string().starts_with("duplicate field `port`"), "{e}");
+}
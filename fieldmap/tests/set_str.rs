@@ -0,0 +1,115 @@
+use fieldmap::{Fields, FromStrItem, SetStrError};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+#[derive(Fields, Debug, PartialEq)]
+#[fields(item = FromStrItem)]
+struct ExampleType {
+    value_u8: u8,
+    #[fields(alias = "s")]
+    value_s: String,
+    value_b: bool,
+}
+
+#[derive(Fields)]
+#[fields(item = FromStrItem)]
+enum ExampleEnum {
+    A { x: u8 },
+    B(f64),
+}
+
+#[test]
+fn test_set_from_str() {
+    let mut x = 1u32;
+    let item: &mut dyn FromStrItem = &mut x;
+    item.set_from_str("10").unwrap();
+    assert_eq!(x, 10);
+}
+
+#[test]
+fn test_set_from_str_error() {
+    let mut x = 1u8;
+    let e = x.set_from_str("300").unwrap_err();
+    assert_eq!(x, 1);
+    assert_eq!(e.ty(), "u8");
+    #[cfg(feature = "alloc")]
+    assert_eq!(e.message(), "number too large to fit in target type");
+}
+
+#[test]
+fn test_set_str() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        value_b: false,
+    };
+    x.set_str("value_u8", "10").unwrap();
+    x.set_str("s", "abc").unwrap();
+    x.set_str("value_b", "true").unwrap();
+    assert_eq!(
+        x,
+        ExampleType {
+            value_u8: 10,
+            value_s: "abc".into(),
+            value_b: true,
+        }
+    );
+}
+
+#[test]
+fn test_set_str_not_found() {
+    let mut x = ExampleType {
+        value_u8: 0,
+        value_s: String::new(),
+        value_b: false,
+    };
+    let e = x.set_str("other", "10").unwrap_err();
+    assert_eq!(e, SetStrError::NotFound { name: "other" });
+    assert_eq!(e.name(), "other");
+    assert_eq!(e.to_string(), "field `other` was not found");
+    #[cfg(feature = "std")]
+    assert!(e.source().is_none());
+}
+
+#[test]
+fn test_set_str_parse_error() {
+    let mut x = ExampleType {
+        value_u8: 10,
+        value_s: "abc".into(),
+        value_b: true,
+    };
+    let e = x.set_str("value_b", "yes").unwrap_err();
+    assert_eq!(e.name(), "value_b");
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        e.to_string(),
+        "field `value_b`: invalid value for type `bool`: provided string was not `true` or `false`"
+    );
+    #[cfg(not(feature = "alloc"))]
+    assert_eq!(
+        e.to_string(),
+        "field `value_b`: invalid value for type `bool`"
+    );
+    #[cfg(feature = "std")]
+    assert!(e.source().is_some());
+    assert_eq!(
+        x,
+        ExampleType {
+            value_u8: 10,
+            value_s: "abc".into(),
+            value_b: true,
+        }
+    );
+}
+
+#[test]
+fn test_set_str_enum() {
+    let mut x = ExampleEnum::A { x: 0 };
+    x.set_str("x", "5").unwrap();
+    assert!(matches!(x, ExampleEnum::A { x: 5 }));
+    assert!(x.set_str("0", "1.5").is_err());
+
+    let mut x = ExampleEnum::B(0.0);
+    x.set_str("0", "1.5").unwrap();
+    assert!(matches!(x, ExampleEnum::B(v) if v == 1.5));
+}
@@ -0,0 +1,50 @@
+use fieldmap::Fields;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+struct NotDebug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct ExampleType<T> {
+    value_u8: u8,
+    #[fields(skip)]
+    cache: NotDebug,
+    value_u16: u16,
+    #[fields(skip)]
+    _phantom: PhantomData<T>,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct TupleType(#[fields(skip)] NotDebug, u8);
+
+#[test]
+fn test_skip() {
+    let mut value = ExampleType::<NotDebug> {
+        value_u8: 10,
+        cache: NotDebug,
+        value_u16: 15,
+        _phantom: PhantomData,
+    };
+    assert_eq!(ExampleType::<NotDebug>::len(), 2);
+    assert_eq!(
+        ExampleType::<NotDebug>::names().collect::<Vec<_>>(),
+        ["value_u8", "value_u16"]
+    );
+    assert_eq!(ExampleType::<NotDebug>::find("cache"), None);
+    assert_eq!(ExampleType::<NotDebug>::find("value_u16"), Some(1));
+    assert_eq!(format!("{:?}", value.get(1).unwrap()), "15");
+    assert!(value.get(2).is_none());
+    assert!(value.get_mut(1).is_some());
+    let _ = &value.cache;
+}
+
+#[test]
+fn test_skip_tuple() {
+    let value = TupleType(NotDebug, 10);
+    assert_eq!(TupleType::len(), 1);
+    assert_eq!(TupleType::name(0), Some("1"));
+    assert_eq!(TupleType::find("1"), Some(0));
+    assert_eq!(format!("{:?}", value.get(0).unwrap()), "10");
+}
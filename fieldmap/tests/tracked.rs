@@ -0,0 +1,167 @@
+use fieldmap::{Fields, Tracked};
+use std::fmt::Debug;
+
+#[derive(Fields, Debug)]
+#[fields(item = Debug)]
+struct Config {
+    host: String,
+    port: u16,
+    #[fields(skip)]
+    skipped: u8,
+    verbose: bool,
+}
+
+#[derive(Fields, Debug)]
+#[fields(item = Debug)]
+enum ExampleEnum {
+    A { x: u8, y: u8 },
+    B(u8),
+}
+
+macro_rules! wide {
+    ($($f:ident)*) => {
+        #[derive(Fields, Default)]
+        #[fields(item = Debug)]
+        struct Wide {
+            $($f: u8,)*
+        }
+    };
+}
+
+wide!(
+    f00 f01 f02 f03 f04 f05 f06 f07 f08 f09 f10 f11 f12 f13 f14 f15 f16 f17 f18 f19
+    f20 f21 f22 f23 f24 f25 f26 f27 f28 f29 f30 f31 f32 f33 f34 f35 f36 f37 f38 f39
+    f40 f41 f42 f43 f44 f45 f46 f47 f48 f49 f50 f51 f52 f53 f54 f55 f56 f57 f58 f59
+    f60 f61 f62 f63 f64 f65 f66 f67 f68 f69
+);
+
+#[test]
+fn test_new_is_clean() {
+    let x = Tracked::new(Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 0,
+        verbose: false,
+    });
+    assert!(!x.is_any_dirty());
+    assert_eq!(x.dirty().count(), 0);
+    assert_eq!(x.port, 80);
+    assert_eq!(x.skipped, 0);
+}
+
+#[test]
+fn test_get_mut() {
+    let mut x = Tracked::new(Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 0,
+        verbose: false,
+    });
+    assert!(x.get_mut(3).is_none());
+    assert!(x.get_mut(1).is_some());
+    assert!(x.is_dirty(1));
+    assert!(!x.is_dirty(0));
+    assert!(!x.is_dirty(1000));
+    assert_eq!(x.dirty().collect::<Vec<_>>(), [1]);
+    assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["port"]);
+}
+
+#[test]
+fn test_iter_mut() {
+    let mut x = Tracked::new(Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 0,
+        verbose: false,
+    });
+    let mut iter = x.iter_mut();
+    assert_eq!(iter.len(), 3);
+    iter.next();
+    iter.next();
+    assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["host", "port"]);
+    x.iter_mut().for_each(drop);
+    assert_eq!(x.dirty().count(), 3);
+}
+
+#[test]
+fn test_clear_dirty() {
+    let mut x = Tracked::new(Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 0,
+        verbose: false,
+    });
+    x.mark_dirty(0);
+    x.mark_dirty(2);
+    x.mark_dirty(5);
+    assert_eq!(x.dirty().collect::<Vec<_>>(), [0, 2]);
+    x.clear_dirty();
+    assert!(!x.is_any_dirty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_on_change() {
+    use std::sync::{Arc, Mutex};
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut x = Tracked::new(Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 0,
+        verbose: false,
+    });
+    let l = log.clone();
+    x.set_on_change(move |idx, name| l.lock().unwrap().push((idx, name)));
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    assert_send_sync(&x);
+    x.get_mut(2);
+    x.mark_dirty(0);
+    x.iter_mut().nth(1);
+    assert_eq!(
+        *log.lock().unwrap(),
+        [(2, "verbose"), (0, "host"), (0, "host"), (1, "port")]
+    );
+}
+
+#[test]
+fn test_into_inner() {
+    let mut x = Tracked::new(Config {
+        host: "localhost".into(),
+        port: 80,
+        skipped: 0,
+        verbose: false,
+    });
+    x.get_mut(0);
+    assert_eq!(x.into_inner().host, "localhost");
+}
+
+#[test]
+fn test_enum_value() {
+    let mut x = Tracked::new(ExampleEnum::A { x: 1, y: 2 });
+    x.get_mut(1);
+    assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["y"]);
+    assert!(matches!(*x, ExampleEnum::A { x: 1, y: 2 }));
+
+    let mut x = Tracked::new(ExampleEnum::B(1));
+    assert!(x.get_mut(1).is_none());
+    x.get_mut(0);
+    assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["0"]);
+}
+
+#[test]
+fn test_wide() {
+    let mut x = Tracked::<Wide, 2>::new_wide(Wide::default());
+    x.get_mut(3);
+    x.get_mut(64);
+    x.get_mut(69);
+    assert_eq!(x.dirty().collect::<Vec<_>>(), [3, 64, 69]);
+    assert_eq!(x.dirty_names().collect::<Vec<_>>(), ["f03", "f64", "f69"]);
+}
+
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Tracked<Config>>();
+    assert_send_sync::<Tracked<Wide, 2>>();
+}
@@ -0,0 +1,61 @@
+use fieldmap::{Field, TryField};
+
+#[derive(Field)]
+enum Shape {
+    Circle { r: f64, label: String },
+    Rect(f64, u32),
+    Empty,
+}
+
+#[derive(Field)]
+enum Labeled {
+    Rect { w: f64, h: f64, label: String },
+    Empty,
+}
+
+#[derive(Field)]
+struct ExampleType {
+    value_u8: u8,
+}
+
+#[test]
+fn test_try_get() {
+    let mut x = Shape::Circle {
+        r: 1.0,
+        label: "c".into(),
+    };
+    assert_eq!(TryField::<f64>::try_get(&x), Some(&1.0));
+    assert_eq!(TryField::<String>::try_get(&x).unwrap(), "c");
+    assert_eq!(TryField::<u32>::try_get(&x), None);
+
+    *TryField::<f64>::try_get_mut(&mut x).unwrap() = 2.0;
+    assert_eq!(TryField::<f64>::try_get(&x), Some(&2.0));
+
+    let x = Shape::Rect(3.0, 4);
+    assert_eq!(TryField::<f64>::try_get(&x), Some(&3.0));
+    assert_eq!(TryField::<u32>::try_get(&x), Some(&4));
+    assert_eq!(TryField::<String>::try_get(&x), None);
+
+    let mut x = Shape::Empty;
+    assert_eq!(TryField::<f64>::try_get(&x), None);
+    assert_eq!(TryField::<f64>::try_get_mut(&mut x), None);
+}
+
+#[test]
+fn test_try_get_duplicate_type() {
+    let x = Labeled::Rect {
+        w: 1.0,
+        h: 2.0,
+        label: "r".into(),
+    };
+    assert_eq!(TryField::<String>::try_get(&x).unwrap(), "r");
+    assert!(matches!(x, Labeled::Rect { w, h, .. } if w * h == 2.0));
+    assert_eq!(TryField::<String>::try_get(&Labeled::Empty), None);
+}
+
+#[test]
+fn test_try_get_struct() {
+    let x = ExampleType { value_u8: 10 };
+    assert_eq!(TryField::<u8>::try_get(&x), Some(&10));
+    assert_eq!(Field::<u8>::get(&x), &10);
+}
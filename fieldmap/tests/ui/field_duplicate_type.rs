@@ -0,0 +1,12 @@
+use fieldmap::Field;
+
+#[derive(Field)]
+struct ExampleType {
+    x: u32,
+    y: u32,
+}
+
+fn main() {
+    let x = ExampleType { x: 1, y: 2 };
+    let _ = Field::<u32>::get(&x);
+}
@@ -0,0 +1,12 @@
+use fieldmap::TryField;
+
+#[derive(fieldmap::Field)]
+enum ExampleType {
+    A(u8),
+    B { x: u8, y: u8 },
+}
+
+fn main() {
+    let x = ExampleType::A(1);
+    let _ = TryField::<u8>::try_get(&x);
+}
@@ -0,0 +1,7 @@
+#[derive(fieldmap::Field)]
+struct ExampleType<T> {
+    value_t: T,
+    value_u32: u32,
+}
+
+fn main() {}
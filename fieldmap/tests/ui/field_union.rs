@@ -0,0 +1,7 @@
+#[derive(fieldmap::Field)]
+union ExampleType {
+    value_u8: u8,
+    value_u16: u16,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug)]
+struct Inner {
+    port: u16,
+}
+
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug)]
+struct Outer {
+    port: u16,
+    #[fields(flatten)]
+    inner: Inner,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug)]
+struct Outer<T: fieldmap::Fields> {
+    #[fields(flatten)]
+    inner: T,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug)]
+struct Inner {
+    host: String,
+}
+
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug)]
+struct Outer {
+    #[fields(rename = "DB_HOST")]
+    x: String,
+    #[fields(flatten, prefix = "db")]
+    db: Inner,
+}
+
+fn main() {}
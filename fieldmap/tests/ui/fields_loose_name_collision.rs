@@ -0,0 +1,9 @@
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug)]
+struct ExampleType {
+    value_u8: u8,
+    #[fields(rename = "valueU8")]
+    value_u16: u16,
+}
+
+fn main() {}
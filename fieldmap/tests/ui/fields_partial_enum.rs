@@ -0,0 +1,7 @@
+#[derive(fieldmap::Fields)]
+#[fields(item = std::fmt::Debug, partial = "PartialShape")]
+enum Shape {
+    Circle { r: f64 },
+}
+
+fn main() {}
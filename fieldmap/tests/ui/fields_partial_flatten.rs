@@ -0,0 +1,14 @@
+#[derive(fieldmap::Fields, Clone)]
+#[fields(item = std::fmt::Debug)]
+struct Inner {
+    x: u8,
+}
+
+#[derive(fieldmap::Fields, Clone)]
+#[fields(item = std::fmt::Debug, partial = "PartialOuter")]
+struct Outer {
+    #[fields(flatten)]
+    inner: Inner,
+}
+
+fn main() {}
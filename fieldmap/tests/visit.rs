@@ -0,0 +1,164 @@
+use fieldmap::{Fields, Path, Visit, Visitor};
+use std::fmt::Debug;
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct DbConfig {
+    host: String,
+    port: u16,
+}
+
+#[derive(Fields, Debug)]
+#[fields(item = Debug)]
+enum Auth {
+    None,
+    Token(String),
+}
+
+#[derive(Fields, Debug)]
+#[fields(item = Debug)]
+struct Window {
+    secs: u64,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct Limits {
+    max: u32,
+    #[fields(nested)]
+    window: Window,
+}
+
+#[derive(Fields)]
+#[fields(item = Debug)]
+struct App {
+    name: String,
+    #[fields(nested)]
+    db: DbConfig,
+    #[fields(nested)]
+    auth: Auth,
+    #[fields(flatten, prefix = "limits.")]
+    limits: Limits,
+    replica: DbConfig,
+}
+
+impl Debug for DbConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.host, self.port)
+    }
+}
+
+#[derive(Default)]
+struct Dump(Vec<String>);
+
+impl Visitor<dyn Debug> for Dump {
+    fn enter_struct(&mut self, path: Option<&Path>) {
+        if let Some(path) = path {
+            self.0.push(format!("enter {}", path));
+        }
+    }
+    fn leaf(&mut self, path: &Path, value: &dyn Debug) {
+        self.0.push(format!("{} = {:?}", path, value));
+    }
+    fn leave_struct(&mut self, path: Option<&Path>) {
+        if let Some(path) = path {
+            self.0.push(format!("leave {}", path));
+        }
+    }
+}
+
+#[test]
+fn test_visit() {
+    let mut d = Dump::default();
+    let x = App {
+        name: "app".into(),
+        db: DbConfig {
+            host: "localhost".into(),
+            port: 5432,
+        },
+        auth: Auth::Token("abc".into()),
+        limits: Limits {
+            max: 10,
+            window: Window { secs: 60 },
+        },
+        replica: DbConfig {
+            host: "replica".into(),
+            port: 5433,
+        },
+    };
+    x.visit(&mut d);
+    assert_eq!(
+        d.0,
+        [
+            "name = \"app\"",
+            "enter db",
+            "db.host = \"localhost\"",
+            "db.port = 5432",
+            "leave db",
+            "enter auth",
+            "auth.0 = \"abc\"",
+            "leave auth",
+            "limits.max = 10",
+            "enter limits.window",
+            "limits.window.secs = 60",
+            "leave limits.window",
+            "replica = replica:5433",
+        ]
+    );
+}
+
+#[test]
+fn test_visit_enum_unit() {
+    let mut d = Dump::default();
+    let x = App {
+        name: "app".into(),
+        db: DbConfig {
+            host: "localhost".into(),
+            port: 5432,
+        },
+        auth: Auth::None,
+        limits: Limits {
+            max: 10,
+            window: Window { secs: 60 },
+        },
+        replica: DbConfig {
+            host: "replica".into(),
+            port: 5433,
+        },
+    };
+    x.visit(&mut d);
+    assert_eq!(d.0[5..7], ["enter auth", "leave auth"]);
+}
+
+#[test]
+fn test_visit_root() {
+    struct Count(usize, usize);
+    impl Visitor<dyn Debug> for Count {
+        fn enter_struct(&mut self, _path: Option<&Path>) {
+            self.0 += 1;
+        }
+        fn leaf(&mut self, _path: &Path, _value: &dyn Debug) {}
+        fn leave_struct(&mut self, _path: Option<&Path>) {
+            self.1 += 1;
+        }
+    }
+    let mut c = Count(0, 0);
+    let x = App {
+        name: "app".into(),
+        db: DbConfig {
+            host: "localhost".into(),
+            port: 5432,
+        },
+        auth: Auth::None,
+        limits: Limits {
+            max: 10,
+            window: Window { secs: 60 },
+        },
+        replica: DbConfig {
+            host: "replica".into(),
+            port: 5433,
+        },
+    };
+    x.visit(&mut c);
+    assert_eq!((c.0, c.1), (4, 4));
+}